@@ -0,0 +1,94 @@
+/// Where a quotation lines up in a run of text: the text tokens it spans
+/// (inclusive) and how much of the quotation's weight the alignment keeps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alignment {
+    pub start: usize,
+    pub end: usize,
+    /// Aligned weight over the quotation's total, from 0 to 1.
+    pub confidence: f64,
+}
+
+/// Cost of each text word an alignment skips over, relative to the average
+/// weight of a quotation word: an added word or two in the remembered wording
+/// costs a little, a long detour costs more than it gains.
+const GAP: f64 = 0.25;
+
+/// Local (Smith–Waterman) alignment of a quotation's tokens against `text`, both
+/// as word stems. A matched word scores its weight (rarer words weigh more); a
+/// quotation word with no counterpart scores nothing, and a skipped text word
+/// costs a gap. Returns the best alignment ending at each text token that ends
+/// one, so a caller can keep the best per verse.
+pub fn align<S: AsRef<str>>(quote: &[String], weights: &[f64], text: &[S]) -> Vec<Alignment> {
+    let total: f64 = weights.iter().sum();
+    if quote.is_empty() || total <= 0.0 {
+        return Vec::new();
+    }
+    let gap = GAP * total / quote.len() as f64;
+    // One column of scores and alignment starts per text token, by quote row.
+    let rows = quote.len() + 1;
+    let mut prev = vec![(0.0f64, 0usize); rows];
+    let mut column = prev.clone();
+    let mut out = Vec::new();
+    for (j, token) in text.iter().enumerate() {
+        let token = token.as_ref();
+        column[0] = (0.0, j);
+        let mut best: Option<(f64, usize)> = None;
+        for i in 1..rows {
+            let mut cell = (0.0, j);
+            if quote[i - 1] == token {
+                let (score, start) = prev[i - 1];
+                let start = if score > 0.0 { start } else { j };
+                cell = (score + weights[i - 1], start);
+            }
+            // The quotation word unmatched: no gain, no cost.
+            if column[i - 1].0 > cell.0 {
+                cell = column[i - 1];
+            }
+            // This text word skipped.
+            if prev[i].0 - gap > cell.0 {
+                cell = (prev[i].0 - gap, prev[i].1);
+            }
+            column[i] = cell;
+            if quote[i - 1] == token && best.is_none_or(|(score, _)| cell.0 > score) {
+                best = Some(cell);
+            }
+        }
+        if let Some((score, start)) = best {
+            out.push(Alignment {
+                start,
+                end: j,
+                confidence: (score / total).min(1.0),
+            });
+        }
+        std::mem::swap(&mut prev, &mut column);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::words;
+
+    fn best(quote: &str, text: &str) -> Alignment {
+        let quote = words(quote);
+        let weights = vec![1.0; quote.len()];
+        align(&quote, &weights, &words(text))
+            .into_iter()
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+            .unwrap()
+    }
+
+    #[test]
+    fn aligns_loosely_quoted_wording() {
+        let text = "for god so loved the world that he gave his only begotten son";
+        let exact = best("god so loved the world", text);
+        assert_eq!((exact.start, exact.end, exact.confidence), (1, 5, 1.0));
+        // A dropped word and a substituted one still line up, at lower confidence.
+        let loose = best("god loved the whole world that he gave", text);
+        assert_eq!((loose.start, loose.end), (1, 8));
+        assert!(loose.confidence > 0.7 && loose.confidence < 1.0);
+        // Scattered shared words don't make a strong match.
+        assert!(best("world son god", text).confidence < 0.7);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::locales::LOCALES;
+use crate::text::{edit_distance, fold_diacritics};
+
 pub struct BookDef {
     pub name: &'static str,
     pub aliases: &'static [&'static str],
@@ -15,17 +18,118 @@ pub fn normalize_book(input: &str) -> Option<&'static str> {
             }
         }
     }
+    // OSIS and USFM codes, so every `RefStyle` reads back (`1Kgs`, `Jhn`).
+    let compact = key.replace(' ', "");
+    if let Some(book) = BOOKS.iter().find(|b| {
+        osis_code(b.name).eq_ignore_ascii_case(&compact)
+            || usfm_code(b.name).eq_ignore_ascii_case(&compact)
+    }) {
+        return Some(book.name);
+    }
+    // Localized names and aliases (`Juan`, `1. Mose`), in any supported language.
+    for locale in LOCALES {
+        for (book, (name, aliases)) in BOOKS.iter().zip(locale.books) {
+            if std::iter::once(name)
+                .chain(aliases.iter())
+                .any(|n| normalize_key(n) == key)
+            {
+                return Some(book.name);
+            }
+        }
+    }
     None
 }
 
+/// Likely intended books for an unrecognized name, best first. Each name or
+/// alias scores by its edit distance from the input (or as one edit when the
+/// input abbreviates it), with a canonical name beating an alias at the same
+/// distance. Only the best-scoring books within a small distance are returned,
+/// at most three.
+pub fn suggest_books(input: &str) -> Vec<&'static str> {
+    let key = normalize_key(input);
+    let len = key.chars().count();
+    if len == 0 {
+        return Vec::new();
+    }
+    let max_distance = if len <= 3 { 1 } else { 2 };
+
+    // Scores are in half-edits: `2 * distance`, plus one for an alias.
+    let mut scored: Vec<(usize, &'static str)> = BOOKS
+        .iter()
+        .filter_map(|book| {
+            let score = std::iter::once(book.name)
+                .chain(book.aliases.iter().copied())
+                .enumerate()
+                .map(|(i, name)| {
+                    let candidate = normalize_key(name);
+                    let mut distance = edit_distance(&key, &candidate);
+                    if len >= 2 && candidate.starts_with(&key) {
+                        distance = distance.min(1);
+                    }
+                    2 * distance + usize::from(i > 0)
+                })
+                .min()?;
+            (score <= 2 * max_distance + 1).then_some((score, book.name))
+        })
+        .collect();
+    // Stable sort keeps canonical order among equal scores.
+    scored.sort_by_key(|(score, _)| *score);
+    let best = scored.first().map(|(score, _)| *score);
+    scored
+        .into_iter()
+        .take_while(|(score, _)| Some(*score) == best)
+        .take(3)
+        .map(|(_, name)| name)
+        .collect()
+}
+
 /// Number of Old Testament books at the front of `BOOKS`.
 pub const OT_BOOK_COUNT: usize = 39;
 
+/// Named canonical groups, as their first and last book.
+const GROUPS: &[(&str, &str, &str)] = &[
+    ("pentateuch", "Genesis", "Deuteronomy"),
+    ("history", "Joshua", "Esther"),
+    ("poetry", "Job", "Song of Solomon"),
+    ("major-prophets", "Isaiah", "Daniel"),
+    ("minor-prophets", "Hosea", "Malachi"),
+    ("gospels", "Matthew", "John"),
+    ("pauline", "Romans", "Philemon"),
+    ("general-epistles", "Hebrews", "Jude"),
+];
+
+/// The names of the canonical groups (`gospels`, `pauline`, ...).
+pub fn group_names() -> impl Iterator<Item = &'static str> {
+    GROUPS.iter().map(|(name, _, _)| *name)
+}
+
+/// A group's first and last book as positions in `BOOKS`, by name
+/// (`Minor Prophets`, `minor_prophets`, and `minor-prophets` all work).
+pub fn group_range(name: &str) -> Option<(usize, usize)> {
+    let name = name.trim().to_lowercase().replace([' ', '_'], "-");
+    let (_, first, last) = GROUPS.iter().find(|(group, _, _)| *group == name)?;
+    let at = |book: &str| book_position(book).expect("group books are canonical");
+    Some((at(first), at(last)))
+}
+
 /// Canonical position of a book (0-based) within `BOOKS`, by canonical name.
 pub fn book_position(name: &str) -> Option<usize> {
     BOOKS.iter().position(|b| b.name == name)
 }
 
+/// Chapters per book in `BOOKS` order, in the common English (KJV)
+/// versification.
+const CHAPTER_COUNTS: [u16; 66] = [
+    50, 40, 27, 36, 34, 24, 21, 4, 31, 24, 22, 25, 29, 36, 10, 13, 10, 42, 150, 31, 12, 8, 66, 52,
+    5, 48, 12, 14, 3, 9, 1, 4, 7, 3, 3, 3, 2, 14, 4, // Old Testament
+    28, 16, 24, 21, 28, 16, 16, 13, 6, 6, 4, 4, 5, 3, 6, 4, 3, 1, 13, 5, 5, 3, 5, 1, 1, 1, 22,
+];
+
+/// How many chapters a canonical book is expected to have.
+pub fn expected_chapters(name: &str) -> Option<u16> {
+    book_position(name).map(|i| CHAPTER_COUNTS[i])
+}
+
 /// `true` for an Old Testament book, `false` for New Testament, `None` if unknown.
 pub fn is_old_testament(name: &str) -> Option<bool> {
     book_position(name).map(|i| i < OT_BOOK_COUNT)
@@ -106,9 +210,111 @@ pub fn osis_code(name: &str) -> &'static str {
     }
 }
 
+/// USFM (Paratext) book code for a canonical book name (e.g. "1 Corinthians" ->
+/// "1CO"). Also the basis of the compact reference style.
+pub fn usfm_code(name: &str) -> &'static str {
+    match name {
+        "Genesis" => "GEN",
+        "Exodus" => "EXO",
+        "Leviticus" => "LEV",
+        "Numbers" => "NUM",
+        "Deuteronomy" => "DEU",
+        "Joshua" => "JOS",
+        "Judges" => "JDG",
+        "Ruth" => "RUT",
+        "1 Samuel" => "1SA",
+        "2 Samuel" => "2SA",
+        "1 Kings" => "1KI",
+        "2 Kings" => "2KI",
+        "1 Chronicles" => "1CH",
+        "2 Chronicles" => "2CH",
+        "Ezra" => "EZR",
+        "Nehemiah" => "NEH",
+        "Esther" => "EST",
+        "Job" => "JOB",
+        "Psalms" => "PSA",
+        "Proverbs" => "PRO",
+        "Ecclesiastes" => "ECC",
+        "Song of Solomon" => "SNG",
+        "Isaiah" => "ISA",
+        "Jeremiah" => "JER",
+        "Lamentations" => "LAM",
+        "Ezekiel" => "EZK",
+        "Daniel" => "DAN",
+        "Hosea" => "HOS",
+        "Joel" => "JOL",
+        "Amos" => "AMO",
+        "Obadiah" => "OBA",
+        "Jonah" => "JON",
+        "Micah" => "MIC",
+        "Nahum" => "NAM",
+        "Habakkuk" => "HAB",
+        "Zephaniah" => "ZEP",
+        "Haggai" => "HAG",
+        "Zechariah" => "ZEC",
+        "Malachi" => "MAL",
+        "Matthew" => "MAT",
+        "Mark" => "MRK",
+        "Luke" => "LUK",
+        "John" => "JHN",
+        "Acts" => "ACT",
+        "Romans" => "ROM",
+        "1 Corinthians" => "1CO",
+        "2 Corinthians" => "2CO",
+        "Galatians" => "GAL",
+        "Ephesians" => "EPH",
+        "Philippians" => "PHP",
+        "Colossians" => "COL",
+        "1 Thessalonians" => "1TH",
+        "2 Thessalonians" => "2TH",
+        "1 Timothy" => "1TI",
+        "2 Timothy" => "2TI",
+        "Titus" => "TIT",
+        "Philemon" => "PHM",
+        "Hebrews" => "HEB",
+        "James" => "JAS",
+        "1 Peter" => "1PE",
+        "2 Peter" => "2PE",
+        "1 John" => "1JN",
+        "2 John" => "2JN",
+        "3 John" => "3JN",
+        "Jude" => "JUD",
+        "Revelation" => "REV",
+        // Unreachable for canonical names; USFM's first "extra book" code.
+        _ => "XXA",
+    }
+}
+
+/// Canonical book name for an OSIS book code, case-insensitively (`1Cor` ->
+/// "1 Corinthians"). The inverse of `osis_code`.
+pub fn book_from_osis(code: &str) -> Option<&'static str> {
+    BOOKS
+        .iter()
+        .find(|b| osis_code(b.name).eq_ignore_ascii_case(code))
+        .map(|b| b.name)
+}
+
+/// Canonical book name for a 1-based book number in Bible order (1 ->
+/// "Genesis", 66 -> "Revelation"), as numbered sources give them.
+pub fn book_by_number(number: usize) -> Option<&'static str> {
+    number
+        .checked_sub(1)
+        .and_then(|i| BOOKS.get(i))
+        .map(|b| b.name)
+}
+
+/// Canonical book name for a USFM book code, case-insensitively (`1CO` ->
+/// "1 Corinthians"). The inverse of `usfm_code`.
+pub fn book_from_usfm(code: &str) -> Option<&'static str> {
+    BOOKS
+        .iter()
+        .find(|b| usfm_code(b.name).eq_ignore_ascii_case(code))
+        .map(|b| b.name)
+}
+
 fn normalize_key(input: &str) -> String {
     let mut out = String::new();
-    for ch in input.chars() {
+    for ch in fold_diacritics(input).chars() {
         if ch.is_ascii_alphanumeric() || ch.is_ascii_whitespace() {
             out.push(ch.to_ascii_lowercase());
         }
@@ -7,7 +7,14 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use crate::books::normalize_book;
+use crate::cli::SourceFormat;
+use crate::corpus;
+use crate::delimited;
+use crate::index;
+use crate::osis;
+use crate::usfm;
 use crate::verses::Verse;
+use crate::zefania;
 
 pub const DEFAULT_TRANSLATION: &str = "kjv";
 
@@ -44,6 +51,21 @@ pub struct Manifest {
     pub source: String,
     pub created_at: String,
     pub verse_count: usize,
+    /// Language code (e.g. "es"); selects localized book names for display.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// The work's title, when the source names it (e.g. an OSIS header).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// A parsed translation source: its verses, plus the title and language when
+/// the format records them.
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub verses: Vec<Verse>,
+    pub title: Option<String>,
+    pub language: Option<String>,
 }
 
 /// Persisted user config (currently just the default translation).
@@ -77,6 +99,11 @@ impl CachePaths {
         self.dir_for(id).join("verses.jsonl")
     }
 
+    /// Inverted search index, rebuilt whenever the manifest changes.
+    pub fn index_path_for(&self, id: &str) -> PathBuf {
+        self.dir_for(id).join("index.bin")
+    }
+
     pub fn manifest_path_for(&self, id: &str) -> PathBuf {
         self.dir_for(id).join("manifest.json")
     }
@@ -86,6 +113,11 @@ impl CachePaths {
         self.verses_path_for(&self.translation)
     }
 
+    /// Search index path for the active translation.
+    pub fn index_path(&self) -> PathBuf {
+        self.index_path_for(&self.translation)
+    }
+
     /// Manifest path for the active translation.
     pub fn manifest_path(&self) -> PathBuf {
         self.manifest_path_for(&self.translation)
@@ -106,9 +138,21 @@ pub fn default_cache_root() -> PathBuf {
     std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
 }
 
-/// Download, normalize, and store a translation under `translations/<id>/`.
+/// Download, normalize, and store a translation under `translations/<id>/`, as
+/// JSONL plus a binary corpus that later reads memory-map, and build its search
+/// index.
 /// When `source` is `None`, a known built-in source is used (error if unknown).
-pub fn preload(paths: &CachePaths, id: &str, source: Option<&str>) -> Result<usize> {
+/// When `language` is `None`, the source's own language is used, or else a
+/// reinstall keeps the previously recorded one. `format` is detected from the
+/// content unless given; `columns` lays out a CSV/TSV source.
+pub fn preload(
+    paths: &CachePaths,
+    id: &str,
+    source: Option<&str>,
+    language: Option<&str>,
+    format: SourceFormat,
+    columns: Option<&str>,
+) -> Result<usize> {
     let source = match source {
         Some(s) => s.to_string(),
         None => known_source(id)
@@ -122,11 +166,27 @@ pub fn preload(paths: &CachePaths, id: &str, source: Option<&str>) -> Result<usi
     fs::create_dir_all(&dir).with_context(|| format!("Failed creating {}", dir.display()))?;
 
     let raw = read_source(&source)?;
-    let verses = normalize_source_to_verses(&raw)
+    let imported = normalize_source(&raw, format, columns)
         .with_context(|| format!("Failed parsing translation source from {}", source))?;
+    let verses = imported.verses;
 
-    write_jsonl(&paths.verses_path_for(id), &verses)?;
-    write_manifest(&paths.manifest_path_for(id), id, &source, verses.len())?;
+    let language = match language.map(str::to_string).or(imported.language) {
+        Some(language) => Some(language),
+        None => read_manifest(&paths.manifest_path_for(id)).and_then(|m| m.language),
+    };
+
+    let verses_path = paths.verses_path_for(id);
+    write_jsonl(&verses_path, &verses)?;
+    corpus::write(&corpus::binary_path(&verses_path), &verses)?;
+    let manifest = write_manifest(
+        &paths.manifest_path_for(id),
+        id,
+        &source,
+        verses.len(),
+        language,
+        imported.title,
+    )?;
+    index::write(&paths.index_path_for(id), &manifest.created_at, &verses)?;
 
     Ok(verses.len())
 }
@@ -198,17 +258,26 @@ pub fn read_manifest(path: &Path) -> Option<Manifest> {
     serde_json::from_str(&raw).ok()
 }
 
-fn write_manifest(path: &Path, id: &str, source: &str, verse_count: usize) -> Result<()> {
+fn write_manifest(
+    path: &Path,
+    id: &str,
+    source: &str,
+    verse_count: usize,
+    language: Option<String>,
+    title: Option<String>,
+) -> Result<Manifest> {
     let manifest = Manifest {
         translation: id.to_string(),
         source: source.to_string(),
         created_at: Utc::now().to_rfc3339(),
         verse_count,
+        language,
+        title,
     };
     let raw = serde_json::to_string_pretty(&manifest)?;
     fs::write(path, raw)
         .with_context(|| format!("Failed writing manifest to {}", path.display()))?;
-    Ok(())
+    Ok(manifest)
 }
 
 fn read_source(source: &str) -> Result<String> {
@@ -219,6 +288,9 @@ fn read_source(source: &str) -> Result<String> {
     }
 
     let path = Path::new(trimmed);
+    if path.is_dir() {
+        return usfm::read_dir(path);
+    }
     if path.exists() {
         return fs::read_to_string(path)
             .with_context(|| format!("Failed reading {}", path.display()));
@@ -259,6 +331,48 @@ fn write_jsonl(path: &Path, verses: &[Verse]) -> Result<()> {
     Ok(())
 }
 
+/// Parse a translation source in any supported format: OSIS or Zefania XML,
+/// USFM, CSV/TSV, or one of the JSON/JSONL shapes.
+fn normalize_source(raw: &str, format: SourceFormat, columns: Option<&str>) -> Result<Imported> {
+    let trimmed = strip_bom(raw).trim_start();
+    let format = match format {
+        SourceFormat::Auto => detect_format(trimmed),
+        format => format,
+    };
+    if columns.is_some() && !matches!(format, SourceFormat::Csv | SourceFormat::Tsv) {
+        bail!("--columns only applies to CSV/TSV sources");
+    }
+    match format {
+        SourceFormat::Osis => osis::parse(trimmed),
+        SourceFormat::Zefania => zefania::parse(trimmed),
+        SourceFormat::Usfm => usfm::parse(trimmed),
+        SourceFormat::Csv => delimited::parse(trimmed, b',', columns),
+        SourceFormat::Tsv => delimited::parse(trimmed, b'\t', columns),
+        SourceFormat::Json | SourceFormat::Auto => Ok(Imported {
+            verses: normalize_source_to_verses(trimmed)?,
+            ..Imported::default()
+        }),
+    }
+}
+
+fn detect_format(raw: &str) -> SourceFormat {
+    if osis::is_osis(raw) {
+        SourceFormat::Osis
+    } else if zefania::is_zefania(raw) {
+        SourceFormat::Zefania
+    } else if usfm::is_usfm(raw) {
+        SourceFormat::Usfm
+    } else if raw.starts_with('{') || raw.starts_with('[') {
+        SourceFormat::Json
+    } else {
+        match delimited::detect_delimiter(raw) {
+            Some(b'\t') => SourceFormat::Tsv,
+            Some(_) => SourceFormat::Csv,
+            None => SourceFormat::Json,
+        }
+    }
+}
+
 fn normalize_source_to_verses(raw: &str) -> Result<Vec<Verse>> {
     let trimmed = strip_bom(raw).trim_start();
     // A file starting with '{' may still be JSONL (one object per line); fall
@@ -519,6 +633,18 @@ mod tests {
         assert_eq!(verses[1].verse, 17);
     }
 
+    #[test]
+    fn maps_localized_book_names_to_canonical() {
+        let raw = r#"[
+            {"name": "Génesis", "chapters": [["En el principio creó Dios los cielos y la tierra."]]},
+            {"name": "1. Mose", "chapters": [["Im Anfang schuf Gott Himmel und Erde."]]},
+            {"name": "Juan", "chapters": [["En el principio era el Verbo."]]}
+        ]"#;
+        let verses = normalize_source_to_verses(raw).unwrap();
+        let books: Vec<&str> = verses.iter().map(|v| v.book.as_str()).collect();
+        assert_eq!(books, vec!["Genesis", "Genesis", "John"]);
+    }
+
     #[test]
     fn known_sources_cover_default_translations() {
         assert!(known_source("kjv").is_some());
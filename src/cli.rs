@@ -3,6 +3,7 @@ use clap_complete::Shell;
 use std::path::PathBuf;
 
 pub use crate::output::Format;
+pub use crate::reference::RefStyle;
 
 #[derive(Parser)]
 #[command(name = "bible", version, about = "A fast, playful Bible CLI (KJV MVP)")]
@@ -25,6 +26,11 @@ pub struct Cli {
     #[arg(long, global = true, conflicts_with_all = ["format", "json"])]
     pub raw: bool,
 
+    /// How references are written in output: full names, SBL abbreviations,
+    /// compact codes, or OSIS ids.
+    #[arg(long, global = true, value_enum, value_name = "STYLE", default_value_t = RefStyle::Full)]
+    pub ref_style: RefStyle,
+
     /// Translation id to read from (default: configured default, else "kjv").
     #[arg(short = 't', long, global = true, value_name = "ID")]
     pub translation: Option<String>,
@@ -50,6 +56,12 @@ impl Cli {
 pub enum Commands {
     Read(ReadArgs),
     Search(SearchArgs),
+    /// Every occurrence of a word in context (KWIC), grouped by book.
+    Concordance(ConcordanceArgs),
+    /// Verses worded most like a given one: parallels and echoes.
+    Similar(SimilarArgs),
+    /// Find where a half-remembered quotation comes from.
+    Whereis(WhereisArgs),
     Today(TodayArgs),
     Random(RandomArgs),
     Echo(EchoArgs),
@@ -69,6 +81,9 @@ pub enum Commands {
     Topic(TopicArgs),
     /// Manage installed translations.
     Translation(TranslationArgs),
+    /// Find, validate, and format scripture references.
+    #[command(visible_alias = "ref")]
+    Refs(RefsArgs),
     /// Generate a shell completion script (bash, zsh, fish, powershell, elvish).
     Completions(CompletionsArgs),
 }
@@ -175,6 +190,41 @@ pub struct TopicArgs {
     pub refs_only: bool,
 }
 
+#[derive(Args)]
+pub struct RefsArgs {
+    #[command(subcommand)]
+    pub action: RefsAction,
+}
+
+#[derive(Subcommand)]
+pub enum RefsAction {
+    /// List every reference in a prose or Markdown file and check it resolves.
+    Scan(RefsScanArgs),
+    /// Rewrite references in a house style (e.g. `bible ref format 1 Cor 13:4 --style sbl`).
+    Format(RefsFormatArgs),
+}
+
+#[derive(Args)]
+pub struct RefsScanArgs {
+    /// File to scan, or `-` for stdin.
+    pub input: String,
+
+    /// Exit non-zero if any reference does not resolve (for linting).
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Args)]
+pub struct RefsFormatArgs {
+    /// One or more references separated by `;`.
+    #[arg(required = true)]
+    pub reference: Vec<String>,
+
+    /// Style to write (default: the global `--ref-style`).
+    #[arg(long, value_enum)]
+    pub style: Option<RefStyle>,
+}
+
 #[derive(Args)]
 pub struct TranslationArgs {
     #[command(subcommand)]
@@ -191,6 +241,9 @@ pub enum TranslationAction {
     Default(TranslationDefaultArgs),
     /// Remove an installed translation.
     Remove(TranslationRemoveArgs),
+    /// Check an installed translation for missing books and chapters, verse
+    /// gaps, duplicates, unknown book names, and empty verses.
+    Verify(TranslationVerifyArgs),
 }
 
 #[derive(Args)]
@@ -198,9 +251,42 @@ pub struct TranslationAddArgs {
     /// Translation id (e.g. `bbe`). Known ids install without `--source`.
     pub id: String,
 
-    /// Source URL or file path (required for unknown ids).
+    /// Source URL, file path, or directory of USFM books (required for
+    /// unknown ids). JSON/JSONL, OSIS, USFM, Zefania, and CSV/TSV are detected.
     #[arg(long)]
     pub source: Option<String>,
+
+    /// Language code of the translation (e.g. `es`, `de`, `fr`, `pt`), so book
+    /// names display in that language.
+    #[arg(long, value_name = "CODE")]
+    pub language: Option<String>,
+
+    /// Source format, when detection guesses wrong.
+    #[arg(long, value_enum, default_value_t = SourceFormat::Auto)]
+    pub source_format: SourceFormat,
+
+    /// Columns of a CSV/TSV source, in order: `book`, `chapter`, `verse`,
+    /// `text`, or `id` (a numeric verse id like 43003016); `-` skips one.
+    #[arg(long, value_name = "LIST")]
+    pub columns: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SourceFormat {
+    /// Detect from the content.
+    Auto,
+    /// A JSON document or JSON Lines.
+    Json,
+    /// OSIS XML.
+    Osis,
+    /// USFM, as a file or a directory of books.
+    Usfm,
+    /// Zefania XML.
+    Zefania,
+    /// Comma-separated values.
+    Csv,
+    /// Tab-separated values.
+    Tsv,
 }
 
 #[derive(Args)]
@@ -215,6 +301,12 @@ pub struct TranslationRemoveArgs {
     pub id: String,
 }
 
+#[derive(Args)]
+pub struct TranslationVerifyArgs {
+    /// Translation id to check (default: the active translation).
+    pub id: Option<String>,
+}
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum ColorMode {
     Auto,
@@ -224,6 +316,7 @@ pub enum ColorMode {
 
 #[derive(Args)]
 pub struct ReadArgs {
+    /// One or more passages separated by `;` (e.g. `John 3:16; Rom 8:28; 12:1-2`).
     #[arg(required = true)]
     pub reference: Vec<String>,
 }
@@ -238,6 +331,17 @@ pub struct SearchArgs {
     #[arg(long, default_value_t = 5)]
     pub limit: usize,
 
+    /// Restrict to a testament.
+    #[arg(long, value_enum)]
+    pub testament: Option<Testament>,
+
+    /// Where to search: installed translation ids, comma-separated (`kjv,bbe`)
+    /// or `all`, to search them together with matches grouped by verse; or
+    /// books, passages, and groups, `;`-separated (`Romans 1-8`, `gospels`,
+    /// `pauline`). Repeat to give both.
+    #[arg(long = "in", value_name = "IDS|SCOPE")]
+    pub within: Vec<String>,
+
     /// Treat the query as a regular expression.
     #[arg(long)]
     pub regex: bool,
@@ -246,9 +350,107 @@ pub struct SearchArgs {
     #[arg(long)]
     pub word: bool,
 
+    /// Match inflected forms too, archaic ones included: `love` finds loveth,
+    /// lovedst, and loving; `give` finds gave, gavest, and giveth.
+    #[arg(long, conflicts_with = "regex")]
+    pub stem: bool,
+
+    /// Tolerate typos: each query word matches verse words within a few edits
+    /// (more for longer words), closest matches first.
+    #[arg(long, conflicts_with_all = ["regex", "stem", "boolean", "word"])]
+    pub fuzzy: bool,
+
+    /// Treat the query as a boolean expression: words, "quoted phrases",
+    /// AND, OR, NOT, parentheses, and NEAR/n (e.g. `grace NEAR/5 faith`).
+    #[arg(long = "bool", conflicts_with_all = ["regex", "word"])]
+    pub boolean: bool,
+
     /// Print only the number of matches across the whole text.
     #[arg(long)]
     pub count: bool,
+
+    /// Print the number of matching verses per book, in Bible order, with a
+    /// bar chart and testament totals.
+    #[arg(long, conflicts_with = "count")]
+    pub facets: bool,
+
+    /// Order matches by relevance (BM25); same as `--sort relevance`.
+    #[arg(long, conflicts_with = "sort")]
+    pub rank: bool,
+
+    /// Order of matches before `--limit` applies [default: canonical, or
+    /// closeness with --fuzzy].
+    #[arg(long, value_enum)]
+    pub sort: Option<SortOrder>,
+
+    /// Seed for a reproducible `--sort random`; implies random order.
+    #[arg(long, conflicts_with = "rank")]
+    pub seed: Option<u64>,
+
+    /// Show N verses of context around each match, like `grep -C`.
+    #[arg(short = 'C', long = "context", default_value_t = 0)]
+    pub context: usize,
+}
+
+#[derive(Args)]
+pub struct ConcordanceArgs {
+    /// The word (or phrase) to look up; matched like `search`.
+    #[arg(value_name = "WORD")]
+    pub term: String,
+
+    #[arg(long)]
+    pub book: Option<String>,
+
+    /// Match whole words only.
+    #[arg(long)]
+    pub word: bool,
+
+    /// Characters of context either side of the keyword.
+    #[arg(long, default_value_t = 30)]
+    pub width: usize,
+}
+
+#[derive(Args)]
+pub struct SimilarArgs {
+    /// A verse or passage (e.g. `1 Kings 10:1`, `Mark 2:1-12`).
+    #[arg(required = true)]
+    pub reference: Vec<String>,
+
+    #[arg(long, default_value_t = 10)]
+    pub limit: usize,
+
+    /// Only look in these books, passages, and groups, `;`-separated
+    /// (`gospels`, `1 Chronicles`).
+    #[arg(long = "in", value_name = "SCOPE")]
+    pub within: Option<String>,
+}
+
+#[derive(Args)]
+pub struct WhereisArgs {
+    /// The wording as remembered; it need not be exact.
+    #[arg(required = true)]
+    pub quote: Vec<String>,
+
+    #[arg(long, default_value_t = 5)]
+    pub limit: usize,
+
+    /// Only look in these books, passages, and groups, `;`-separated.
+    #[arg(long = "in", value_name = "SCOPE")]
+    pub within: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// Bible order (Genesis to Revelation).
+    Canonical,
+    /// Best match first, scored with BM25 over the active translation.
+    Relevance,
+    /// Shortest verse first.
+    Shortest,
+    /// Shuffled (reproducible with `--seed`).
+    Random,
+    /// Fewest typos first, for `--fuzzy` (its default order).
+    Closeness,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -268,6 +470,11 @@ pub struct TodayArgs {
     /// Restrict to a testament.
     #[arg(long, value_enum)]
     pub testament: Option<Testament>,
+
+    /// Restrict to books, passages, and groups, `;`-separated (`gospels`,
+    /// `Romans 1-8`, `minor-prophets`).
+    #[arg(long = "in", value_name = "SCOPE")]
+    pub within: Option<String>,
 }
 
 #[derive(Args)]
@@ -284,6 +491,11 @@ pub struct RandomArgs {
     #[arg(long, value_enum)]
     pub testament: Option<Testament>,
 
+    /// Restrict to books, passages, and groups, `;`-separated (`gospels`,
+    /// `Romans 1-8`, `minor-prophets`).
+    #[arg(long = "in", value_name = "SCOPE")]
+    pub within: Option<String>,
+
     /// Only verses with at most this many words.
     #[arg(long)]
     pub max_words: Option<usize>,
@@ -325,6 +537,14 @@ pub struct CacheArgs {
     #[arg(long)]
     pub source: Option<String>,
 
+    /// Source format, when detection guesses wrong.
+    #[arg(long, value_enum, default_value_t = SourceFormat::Auto)]
+    pub source_format: SourceFormat,
+
+    /// Columns of a CSV/TSV source (see `translation add --columns`).
+    #[arg(long, value_name = "LIST")]
+    pub columns: Option<String>,
+
     /// Show installed translations and cache sizes.
     #[arg(long)]
     pub status: bool,
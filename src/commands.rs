@@ -5,37 +5,64 @@ use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::{thread_rng, SeedableRng};
 use regex::RegexBuilder;
-use std::io::{self, Write};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
 use tokio::io::{AsyncBufReadExt, BufReader};
 
 use crate::ai::{AiProvider, ChatMessage, ProviderRequest, StreamEvent};
-use crate::books::{is_old_testament, normalize_book, osis_code};
+use crate::align::{align, Alignment};
+use crate::books::{group_range, osis_code};
 use crate::cache::{
-    installed_translations, preload, read_manifest, remove_translation, save_default_translation,
-    CachePaths,
+    installed_translations, known_source, preload, read_manifest, remove_translation,
+    save_default_translation, CachePaths,
 };
 use crate::cli::{
-    AiArgs, CacheArgs, DiffArgs, EchoArgs, ExportArgs, ExportTarget, MoodArgs, ParallelArgs,
-    PlanAction, PlanArgs, PlanDoneArgs, PlanTodayArgs, RandomArgs, ReadArgs, SearchArgs, Testament,
-    TodayArgs, TopicArgs, TranslationAction, TranslationArgs, TuiArgs,
+    AiArgs, CacheArgs, ConcordanceArgs, DiffArgs, EchoArgs, ExportArgs, ExportTarget, MoodArgs,
+    ParallelArgs, PlanAction, PlanArgs, PlanDoneArgs, PlanTodayArgs, RandomArgs, ReadArgs,
+    RefStyle, RefsAction, RefsArgs, RefsFormatArgs, RefsScanArgs, SearchArgs, SimilarArgs,
+    SortOrder, TodayArgs, TopicArgs, TranslationAction, TranslationArgs, TuiArgs, WhereisArgs,
 };
+use crate::corpus;
+use crate::index::{self, intersect, word_spans, words, SearchIndex};
 use crate::moods::{all_moods, find_mood};
-use crate::output::{MarkdownRenderer, OutputStyle, ThinkingIndicator};
+use crate::output::{
+    MarkdownRenderer, Occurrence, OutputStyle, QuoteSource, SearchHit, ThinkingIndicator,
+};
 use crate::plans::{
     all_plans, build_days, clear_state, find_plan, load_state, portion_label, save_state, PlanDef,
     PlanState,
 };
-use crate::reference::{parse_reference, ReferenceQuery};
+use crate::query::{parse_query, Query};
+use crate::rank::{cosine, Bm25, Term, TfIdf};
+use crate::reference::{
+    find_references, lookup_book, parse_reference, parse_references, RangeEnd, ReferenceQuery,
+};
+use crate::scope::{touches_book, Scope};
+use crate::stem::stem;
+use crate::text::edit_distance;
 use crate::topics::{all_topics, find_topic};
 use crate::tui;
-use crate::verses::{load_verses, max_chapter, Verse, VerseIndex};
+use crate::verify::{verify, Issue};
+use crate::verses::{
+    canonical_order, load_books, load_positions, load_verses, max_chapter, Verse, VerseIndex,
+};
 
 pub fn run_cache(args: &CacheArgs, paths: &CachePaths) -> Result<()> {
     let id = &paths.translation;
 
     if args.preload {
-        let count = preload(paths, id, args.source.as_deref())?;
+        let count = preload(
+            paths,
+            id,
+            args.source.as_deref(),
+            None,
+            args.source_format,
+            args.columns.as_deref(),
+        )?;
         println!("{} cached: {} verses", id.to_uppercase(), count);
+        warn_integrity(paths, id);
         return Ok(());
     }
 
@@ -51,8 +78,12 @@ pub fn run_cache(args: &CacheArgs, paths: &CachePaths) -> Result<()> {
                 id.to_uppercase(),
                 manifest.verse_count
             );
+            if let Some(title) = &manifest.title {
+                println!("Title: {}", title);
+            }
             println!("Source: {}", manifest.source);
             println!("Updated: {}", manifest.created_at);
+            println!("Format: {}", corpus_format(&paths.verses_path()));
         } else {
             println!("{}: ready", id.to_uppercase());
         }
@@ -76,21 +107,39 @@ fn run_cache_status(paths: &CachePaths) -> Result<()> {
     // A leading "*" marks the active translation.
     for t in installed {
         let marker = if t.id == paths.translation { "*" } else { " " };
+        let format = corpus_format(&paths.verses_path_for(&t.id));
         match t.manifest {
             Some(m) => println!(
-                "{} {:<6} {} verses, {}  (updated {})",
+                "{} {:<6} {} verses, {}, {}  (updated {})",
                 marker,
                 t.id,
                 m.verse_count,
                 human_size(t.size_bytes),
+                format,
                 m.created_at
             ),
-            None => println!("{} {:<6} {}", marker, t.id, human_size(t.size_bytes)),
+            None => println!(
+                "{} {:<6} {}, {}",
+                marker,
+                t.id,
+                human_size(t.size_bytes),
+                format
+            ),
         }
     }
     Ok(())
 }
 
+/// Which on-disk format reads of a translation use: the memory-mapped binary
+/// corpus when preload wrote a current one, otherwise the JSONL it falls back to.
+fn corpus_format(verses_path: &Path) -> &'static str {
+    if corpus::open_current(verses_path).is_some() {
+        "binary"
+    } else {
+        "jsonl"
+    }
+}
+
 fn human_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
     let mut size = bytes as f64;
@@ -116,14 +165,29 @@ fn missing_cache_msg(id: &str) -> String {
 }
 
 pub fn run_read(args: &ReadArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
-    let reference = parse_reference(&args.reference)?;
-    let verses =
-        load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
+    let references = parse_references(&args.reference)?;
+    // Only the books the passages touch are loaded.
+    let verses = load_books(&paths.verses_path(), |book| {
+        references.iter().any(|r| touches_book(r, book))
+    })
+    .with_context(|| missing_cache_msg(&paths.translation))?;
     let index = VerseIndex::build(&verses);
 
+    // Several passages (`John 3:16; Ps 23`) are emitted together, whole books
+    // included in full.
+    let [reference] = references.as_slice() else {
+        let mut passages = Vec::with_capacity(references.len());
+        for reference in &references {
+            let selected = resolve_selection(&index, &verses, reference)?;
+            passages.push((reference, selected));
+        }
+        output.emit_passages(&passages);
+        return Ok(());
+    };
+
     // Whole-book reference: a chapter overview in the human view, or the full
     // book as data in structured formats.
-    if reference.chapter.is_none() {
+    if reference.is_whole_book() {
         if output.is_structured() {
             let book_verses = book_verses(&verses, &reference.book);
             if book_verses.is_empty() {
@@ -132,14 +196,26 @@ pub fn run_read(args: &ReadArgs, paths: &CachePaths, output: &OutputStyle) -> Re
             output.emit_verses(&book_verses);
             return Ok(());
         }
-        return print_book_overview(&verses, &reference);
+        return print_book_overview(&verses, reference, output);
     }
 
-    let selected = index.resolve(&reference)?;
-    output.emit_verses(&selected);
+    let selected = index.resolve(reference)?;
+    output.emit_reference(reference, &selected);
     Ok(())
 }
 
+/// Verse counts per book, for verses already in canonical order.
+fn book_counts<'a>(verses: &[&'a Verse]) -> Vec<(&'a str, usize)> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for v in verses {
+        match counts.last_mut() {
+            Some((book, count)) if *book == v.book => *count += 1,
+            _ => counts.push((&v.book, 1)),
+        }
+    }
+    counts
+}
+
 /// All verses of a book in canonical order.
 fn book_verses<'a>(verses: &'a [Verse], book: &str) -> Vec<&'a Verse> {
     let mut out: Vec<&Verse> = verses.iter().filter(|v| v.book == book).collect();
@@ -148,77 +224,827 @@ fn book_verses<'a>(verses: &'a [Verse], book: &str) -> Vec<&'a Verse> {
 }
 
 pub fn run_search(args: &SearchArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
+    let matcher = build_matcher(args)?;
+    let (translations, within) = split_within(&args.within, paths)?;
+    let scope = Scope::new(args.book.as_deref(), args.testament, within.as_deref())?;
+    if !translations.is_empty() {
+        return run_search_across(args, &translations, &matcher, &scope, paths, output);
+    }
+
+    // Context and relevance scores need every verse, not just the candidates.
+    let whole = args.context > 0 || args.rank || args.sort == Some(SortOrder::Relevance);
+    let index = search_index(&matcher, paths);
+    let (verses, candidates) = search_verses(&matcher, index.as_ref(), paths, whole)?;
+    let mut matches = matching_verses(&matcher, candidates.as_deref(), &verses, &scope);
+
+    if args.count {
+        println!("{}", matches.len());
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        if !output.is_structured() {
+            println!("No matches found.");
+        }
+        return Ok(());
+    }
+
+    if args.facets {
+        matches.sort_by_key(|v| canonical_order(v));
+        output.emit_facets(&book_counts(&matches));
+        return Ok(());
+    }
+
+    if args.seed.is_some() && args.sort.is_some_and(|sort| sort != SortOrder::Random) {
+        bail!("--seed only applies to --sort random");
+    }
+    let sort = if args.rank {
+        SortOrder::Relevance
+    } else if let Some(sort) = args.sort {
+        sort
+    } else if args.seed.is_some() {
+        SortOrder::Random
+    } else if args.fuzzy {
+        SortOrder::Closeness
+    } else {
+        SortOrder::Canonical
+    };
+    match sort {
+        SortOrder::Canonical => matches.sort_by_key(|v| canonical_order(v)),
+        SortOrder::Shortest => {
+            matches.sort_by_key(|v| (v.text.chars().count(), canonical_order(v)))
+        }
+        SortOrder::Random => match args.seed {
+            Some(seed) => matches.shuffle(&mut StdRng::seed_from_u64(seed)),
+            None => matches.shuffle(&mut thread_rng()),
+        },
+        SortOrder::Closeness => {
+            matches.sort_by_cached_key(|v| (matcher.distance(&v.text), canonical_order(v)))
+        }
+        SortOrder::Relevance => {
+            let terms = matcher.score_terms(index.as_ref(), &verses);
+            let bm25 = Bm25::new(&verses);
+            let mut scored: Vec<(&Verse, f64)> = matches
+                .iter()
+                .map(|v| (*v, bm25.score(&v.text, &terms)))
+                .collect();
+            scored.sort_by(|a, b| {
+                b.1.total_cmp(&a.1)
+                    .then_with(|| canonical_order(a.0).cmp(&canonical_order(b.0)))
+            });
+            scored.truncate(args.limit);
+            let (matches, scores): (Vec<&Verse>, Vec<f64>) = scored.into_iter().unzip();
+            emit_search_hits(args, &matcher, &verses, &matches, Some(&scores), output);
+            return Ok(());
+        }
+    }
+
+    matches.truncate(args.limit);
+    emit_search_hits(args, &matcher, &verses, &matches, None, output);
+    Ok(())
+}
+
+/// `similar`: the verses whose wording is closest to a passage's, by TF-IDF
+/// cosine similarity over word stems, so inflected forms still line up. The
+/// passage's own verses are left out.
+pub fn run_similar(args: &SimilarArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
+    let reference = parse_reference(&args.reference)?;
+    let scope = Scope::new(None, None, args.within.as_deref())?;
     let verses =
         load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
+    let index = VerseIndex::build(&verses);
+    let selected = index.resolve(&reference)?;
+
+    let (docs, tfidf) = stem_weights(&verses);
+    let passage: Vec<String> = selected.iter().flat_map(|v| stems_of(&v.text)).collect();
+    let query = tfidf.weights(&passage);
 
-    let book_filter = match args.book.as_ref() {
-        Some(book) => {
-            let normalized =
-                normalize_book(book).ok_or_else(|| anyhow::anyhow!("Unknown book: {}", book))?;
-            Some(normalized.to_string())
+    let passage_verses: HashSet<(&str, u16, u16)> = selected
+        .iter()
+        .map(|v| (v.book.as_str(), v.chapter, v.verse))
+        .collect();
+    let mut scored: Vec<(&Verse, f64)> = verses
+        .iter()
+        .zip(&docs)
+        .filter(|(v, _)| {
+            scope.contains(v) && !passage_verses.contains(&(v.book.as_str(), v.chapter, v.verse))
+        })
+        .map(|(v, doc)| (v, cosine(&query, &tfidf.weights(doc))))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    if scored.is_empty() {
+        if !output.is_structured() {
+            println!("No similar verses found.");
         }
-        None => None,
-    };
+        return Ok(());
+    }
+    scored.sort_by(|a, b| {
+        b.1.total_cmp(&a.1)
+            .then_with(|| canonical_order(a.0).cmp(&canonical_order(b.0)))
+    });
+    scored.truncate(args.limit);
 
-    let matcher = build_matcher(args)?;
+    // Highlight the distinctive words a result shares with the passage.
+    let distinctive = passage
+        .into_iter()
+        .filter(|t| !tfidf.is_common(t))
+        .collect();
+    let shared = Matcher::Stem(distinctive, true);
+    let hits: Vec<SearchHit> = scored
+        .into_iter()
+        .map(|(verse, score)| SearchHit {
+            verse,
+            spans: shared.spans(&verse.text),
+            score: Some(score),
+            context: false,
+        })
+        .collect();
+    output.emit_hits(&[hits], false);
+    Ok(())
+}
 
-    // Scan the whole corpus so counts and ordering are complete, then limit for
-    // display (unless --count, which reports the full total).
-    let mut matches: Vec<&Verse> = Vec::new();
-    for verse in &verses {
-        if let Some(ref book) = book_filter {
-            if &verse.book != book {
-                continue;
+/// Sources scoring below this share of the quotation's weight aren't reported.
+const MIN_CONFIDENCE: f64 = 0.3;
+
+/// `whereis`: the passages a loosely quoted wording most likely comes from.
+/// Each chapter is aligned against the quotation as one run of word stems, so
+/// a quotation may start in one verse and end in the next; rare words count
+/// for more than common ones. Overlapping finds keep only the best.
+pub fn run_whereis(args: &WhereisArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
+    let quote = stems_of(&args.quote.join(" "));
+    if quote.is_empty() {
+        bail!("Give some of the wording to look for");
+    }
+    let scope = Scope::new(None, None, args.within.as_deref())?;
+    let verses =
+        load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
+    let (docs, tfidf) = stem_weights(&verses);
+    let found = quote_sources(&quote, &verses, &docs, &tfidf, &scope, args.limit)?;
+
+    let quoted: HashSet<&str> = quote.iter().map(String::as_str).collect();
+    let sources: Vec<QuoteSource> = found
+        .into_iter()
+        .map(|found| {
+            let (first_word, last_word) = found.words;
+            let last = found.positions.len() - 1;
+            let hits = found
+                .positions
+                .iter()
+                .enumerate()
+                .map(|(k, &p)| {
+                    let verse = &verses[p];
+                    // Mark the quotation's words within the aligned run.
+                    let from = if k == 0 { first_word } else { 0 };
+                    let to = if k == last { last_word } else { usize::MAX };
+                    let spans = word_spans(&verse.text)
+                        .into_iter()
+                        .zip(&docs[p])
+                        .enumerate()
+                        .filter(|(w, (_, stem))| {
+                            from <= *w && *w <= to && quoted.contains(stem.as_str())
+                        })
+                        .map(|(_, (span, _))| span)
+                        .collect();
+                    SearchHit {
+                        verse,
+                        spans,
+                        score: None,
+                        context: false,
+                    }
+                })
+                .collect();
+            QuoteSource {
+                hits,
+                confidence: found.confidence,
             }
+        })
+        .collect();
+
+    if sources.is_empty() {
+        if !output.is_structured() {
+            println!("No likely source found.");
         }
-        if matcher.is_match(&verse.text) {
-            matches.push(verse);
+        return Ok(());
+    }
+    output.emit_quote_sources(&sources);
+    Ok(())
+}
+
+/// A candidate source of a quotation: consecutive verse positions, the
+/// confidence, and the aligned run's bounds as word numbers within the first
+/// and last verse.
+struct Found {
+    positions: Vec<usize>,
+    confidence: f64,
+    words: (usize, usize),
+}
+
+/// Up to `limit` sources of a quotation in `verses` (tokenized as `docs`),
+/// best first and without overlaps. A quotation of common words alone can't
+/// point anywhere and is refused; one weighing less than a single rare word
+/// has its confidence scaled down, so a short stock phrase doesn't place with
+/// certainty wherever it occurs.
+fn quote_sources(
+    quote: &[String],
+    verses: &[Verse],
+    docs: &[Vec<String>],
+    tfidf: &TfIdf,
+    scope: &Scope,
+    limit: usize,
+) -> Result<Vec<Found>> {
+    if quote.iter().all(|term| tfidf.is_common(term)) {
+        bail!("That wording is too common to place; give more of the quotation");
+    }
+    let weights: Vec<f64> = quote.iter().map(|term| tfidf.idf(term)).collect();
+    let distinctive = (weights.iter().sum::<f64>() / tfidf.rarest()).min(1.0);
+
+    let mut order: Vec<usize> = (0..verses.len())
+        .filter(|&i| scope.contains(&verses[i]))
+        .collect();
+    order.sort_by_key(|&i| canonical_order(&verses[i]));
+
+    let mut found: Vec<Found> = Vec::new();
+    for chapter in order.chunk_by(|&a, &b| {
+        verses[a].book == verses[b].book && verses[a].chapter == verses[b].chapter
+    }) {
+        let mut text: Vec<&str> = Vec::new();
+        let mut owner: Vec<(usize, usize)> = Vec::new();
+        for (k, &i) in chapter.iter().enumerate() {
+            for (w, token) in docs[i].iter().enumerate() {
+                text.push(token);
+                owner.push((k, w));
+            }
+        }
+        // The best alignment ending in each verse.
+        let mut by_end: Vec<Option<Alignment>> = vec![None; chapter.len()];
+        for a in align(quote, &weights, &text) {
+            let slot = &mut by_end[owner[a.end].0];
+            if slot.is_none_or(|b| a.confidence > b.confidence) {
+                *slot = Some(a);
+            }
+        }
+        for a in by_end.into_iter().flatten() {
+            let confidence = a.confidence * distinctive;
+            if confidence >= MIN_CONFIDENCE {
+                let (first, last) = (owner[a.start], owner[a.end]);
+                found.push(Found {
+                    positions: chapter[first.0..=last.0].to_vec(),
+                    confidence,
+                    words: (first.1, last.1),
+                });
+            }
+        }
+    }
+    found.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| a.positions[0].cmp(&b.positions[0]))
+    });
+
+    let mut taken: Vec<usize> = Vec::new();
+    let mut sources = Vec::new();
+    for source in found {
+        if sources.len() == limit {
+            break;
+        }
+        if source.positions.iter().any(|p| taken.contains(p)) {
+            continue;
+        }
+        taken.extend(&source.positions);
+        sources.push(source);
+    }
+    Ok(sources)
+}
+
+/// Every verse `matcher` accepts, in stored order: among the `candidates`
+/// positions when given, else among all of `verses`. The matcher confirms each
+/// one. The whole pool is scanned so counts and ordering are complete; callers
+/// limit for display.
+fn matching_verses<'a>(
+    matcher: &Matcher,
+    candidates: Option<&[usize]>,
+    verses: &'a [Verse],
+    scope: &Scope,
+) -> Vec<&'a Verse> {
+    let pool: Vec<&Verse> = match candidates {
+        Some(positions) => positions.iter().filter_map(|&i| verses.get(i)).collect(),
+        None => verses.iter().collect(),
+    };
+    pool.into_iter()
+        .filter(|v| scope.contains(v))
+        .filter(|v| matcher.is_match(&v.text))
+        .collect()
+}
+
+/// Split `search --in` values into translation ids and a scope. A value names
+/// translations when it is `all` or every comma-separated id in it is installed;
+/// otherwise it is a scope (`Romans 1-8`, `gospels`), and several scopes are
+/// joined as alternatives. A known translation that isn't installed is an
+/// error rather than an unknown book.
+fn split_within(within: &[String], paths: &CachePaths) -> Result<(Vec<String>, Option<String>)> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut scopes: Vec<&str> = Vec::new();
+    for value in within {
+        if value.trim() == "all" {
+            ids.extend(installed_translations(paths).into_iter().map(|t| t.id));
+            if ids.is_empty() {
+                bail!("No translations installed. Run `bible translation add <id>`.");
+            }
+            continue;
+        }
+        let listed: Vec<&str> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        match listed.iter().find(|id| !paths.is_installed(id)) {
+            None if !listed.is_empty() => ids.extend(listed.iter().map(|id| id.to_string())),
+            // Some ids are installed, so this is a translation list with a typo
+            // or a missing translation rather than a scope.
+            Some(id) if listed.iter().any(|id| paths.is_installed(id)) => bail!(
+                "{} is not installed. Run `bible translation add {}`.",
+                id.to_uppercase(),
+                id
+            ),
+            _ => {
+                // A downloadable translation that isn't installed yet, rather
+                // than a book or group of that name.
+                let missing = listed.iter().find(|id| {
+                    known_source(id).is_some()
+                        && group_range(id).is_none()
+                        && lookup_book(id).is_err()
+                });
+                if let Some(id) = missing {
+                    bail!(
+                        "{} is not installed. Run `bible translation add {}`.",
+                        id.to_uppercase(),
+                        id
+                    );
+                }
+                scopes.push(value)
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    let scope = (!scopes.is_empty()).then(|| scopes.join(";"));
+    Ok((ids, scope))
+}
+
+/// `search --in kjv,bbe`: run one matcher over several translations and group
+/// the matches by verse, in Bible order. `--limit` counts verses, not matches.
+fn run_search_across(
+    args: &SearchArgs,
+    ids: &[String],
+    matcher: &Matcher,
+    scope: &Scope,
+    paths: &CachePaths,
+    output: &OutputStyle,
+) -> Result<()> {
+    if args.rank || args.sort.is_some() || args.seed.is_some() || args.context > 0 || args.facets {
+        bail!("Searching several translations lists verses in Bible order; --rank, --sort, --seed, -C, and --facets need a single translation");
+    }
+
+    let mut loaded: Vec<Vec<Verse>> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !paths.is_installed(id) {
+            bail!(
+                "{} is not installed. Run `bible translation add {}`.",
+                id.to_uppercase(),
+                id
+            );
+        }
+        let translation_paths = CachePaths::new(paths.root.clone(), id.clone());
+        let index = search_index(matcher, &translation_paths);
+        let (verses, _) = search_verses(matcher, index.as_ref(), &translation_paths, false)?;
+        loaded.push(verses);
+    }
+
+    // Matches keyed by verse in canonical order (book name breaks ties between
+    // books outside the canon), each with the translations that matched.
+    let mut by_verse = BTreeMap::new();
+    let mut counts = Vec::with_capacity(ids.len());
+    for (id, verses) in ids.iter().zip(&loaded) {
+        let matches = matching_verses(matcher, None, verses, scope);
+        counts.push(matches.len());
+        for v in matches {
+            by_verse
+                .entry((canonical_order(v), v.book.as_str()))
+                .or_insert_with(Vec::new)
+                .push((id.as_str(), v));
         }
     }
 
     if args.count {
-        println!("{}", matches.len());
+        let width = ids.iter().map(|id| id.len()).max().unwrap_or(3);
+        for (id, count) in ids.iter().zip(counts) {
+            println!("{:width$}  {}", id, count, width = width);
+        }
         return Ok(());
     }
 
-    if matches.is_empty() {
+    if by_verse.is_empty() {
         if !output.is_structured() {
             println!("No matches found.");
         }
         return Ok(());
     }
 
-    matches.truncate(args.limit);
-    output.emit_verses(&matches);
+    let groups: Vec<Vec<(&str, SearchHit)>> = by_verse
+        .into_values()
+        .take(args.limit)
+        .map(|hits| {
+            hits.into_iter()
+                .map(|(id, verse)| {
+                    let hit = SearchHit {
+                        verse,
+                        spans: matcher.spans(&verse.text),
+                        score: None,
+                        context: false,
+                    };
+                    (id, hit)
+                })
+                .collect()
+        })
+        .collect();
+    output.emit_translation_hits(&groups);
     Ok(())
 }
 
-/// A compiled query matcher: substring (default), whole-word, or full regex.
-/// All matching is case-insensitive.
+/// Print matches with their matched spans, each in a window of `-C` verses from
+/// its chapter. Windows that overlap or touch the previous one are joined.
+fn emit_search_hits<'a>(
+    args: &SearchArgs,
+    matcher: &Matcher,
+    verses: &'a [Verse],
+    matches: &[&'a Verse],
+    scores: Option<&[f64]>,
+    output: &OutputStyle,
+) {
+    let hit = |verse: &'a Verse, n: usize| SearchHit {
+        verse,
+        spans: matcher.spans(&verse.text),
+        score: scores.map(|s| s[n]),
+        context: false,
+    };
+    if args.context == 0 {
+        let hits = matches.iter().enumerate().map(|(n, v)| hit(v, n)).collect();
+        output.emit_hits(&[hits], false);
+        return;
+    }
+
+    let index = VerseIndex::build(verses);
+    // Which result each matched verse is, by reference, so a window's verses
+    // are told apart from context without searching the matches for each one.
+    let numbered: HashMap<(&str, u16, u16), usize> = matches
+        .iter()
+        .enumerate()
+        .map(|(n, v)| ((v.book.as_str(), v.chapter, v.verse), n))
+        .collect();
+    // Each group: its chapter's verses and the window shown.
+    let mut windows: Vec<(Vec<&Verse>, usize, usize)> = Vec::new();
+    for verse in matches {
+        let chapter_verses = index.chapter(&verse.book, verse.chapter);
+        let Some(position) = chapter_verses.iter().position(|v| v.verse == verse.verse) else {
+            continue;
+        };
+        let (start, end) = context_window(chapter_verses.len(), position, args.context);
+        if let Some((last, last_start, last_end)) = windows.last_mut() {
+            let same_chapter = last[0].book == verse.book && last[0].chapter == verse.chapter;
+            if same_chapter && start <= *last_end + 1 && *last_start <= end + 1 {
+                *last_start = (*last_start).min(start);
+                *last_end = (*last_end).max(end);
+                continue;
+            }
+        }
+        windows.push((chapter_verses, start, end));
+    }
+
+    let groups: Vec<Vec<SearchHit>> = windows
+        .iter()
+        .map(|(chapter_verses, start, end)| {
+            chapter_verses[*start..=*end]
+                .iter()
+                .map(
+                    |v| match numbered.get(&(v.book.as_str(), v.chapter, v.verse)) {
+                        Some(&n) => hit(v, n),
+                        None => SearchHit {
+                            verse: v,
+                            spans: Vec::new(),
+                            score: None,
+                            context: true,
+                        },
+                    },
+                )
+                .collect()
+        })
+        .collect();
+    output.emit_hits(&groups, true);
+}
+
+/// The inclusive range of `size` neighbours either side of `position` in a
+/// chapter of `len` verses.
+fn context_window(len: usize, position: usize, size: usize) -> (usize, usize) {
+    (
+        position.saturating_sub(size),
+        (position + size).min(len - 1),
+    )
+}
+
+/// A compiled query matcher: substring (default), whole-word, full regex, or a
+/// boolean query, optionally over word stems. All matching is case-insensitive.
 enum Matcher {
-    Substring(String),
+    /// A literal query: lowercased, and as a case-insensitive regex for spans.
+    Substring(String, regex::Regex),
+    /// A literal whole-word query: the lowercased query and its `\b` regex.
+    Word(String, regex::Regex),
     Regex(regex::Regex),
+    /// `--stem`: the query's word stems, matched against the stems of
+    /// consecutive verse words, whole (with `--word`) or within.
+    Stem(Vec<String>, bool),
+    /// `--fuzzy`: the query's lowercased words, matched against consecutive
+    /// verse words within an edit budget.
+    Fuzzy(Vec<String>),
+    /// `--bool`; with `--stem` its words are stems.
+    Query {
+        query: Query,
+        stemmed: bool,
+    },
 }
 
 impl Matcher {
     fn is_match(&self, text: &str) -> bool {
         match self {
-            Matcher::Substring(needle) => text.to_lowercase().contains(needle),
-            Matcher::Regex(re) => re.is_match(text),
+            Matcher::Substring(needle, _) => text.to_lowercase().contains(needle),
+            Matcher::Word(_, re) | Matcher::Regex(re) => re.is_match(text),
+            Matcher::Stem(stems, whole_words) => {
+                let text = stems_of(text);
+                text.windows(stems.len()).any(|window| {
+                    window
+                        .iter()
+                        .zip(stems)
+                        .all(|(word, stem)| word_matches(word, stem, *whole_words))
+                })
+            }
+            Matcher::Fuzzy(_) => self.distance(text).is_some(),
+            Matcher::Query { query, stemmed } => query.matches(&if *stemmed {
+                stems_of(text)
+            } else {
+                words(text)
+            }),
         }
     }
+
+    /// For `--fuzzy`, the fewest edits that make the query appear in `text`
+    /// (`None` if it is out of reach); 0 for every other matcher.
+    fn distance(&self, text: &str) -> Option<usize> {
+        let Matcher::Fuzzy(terms) = self else {
+            return Some(0);
+        };
+        words(text)
+            .windows(terms.len())
+            .filter_map(|window| {
+                window
+                    .iter()
+                    .zip(terms)
+                    .map(|(word, term)| fuzzy_distance(word, term))
+                    .sum::<Option<usize>>()
+            })
+            .min()
+    }
+
+    /// Byte ranges of `text` that matched, ascending and disjoint. Stem and
+    /// boolean queries mark each word that matches one of the query's terms.
+    fn spans(&self, text: &str) -> Vec<(usize, usize)> {
+        let words_where = |accepts: &dyn Fn(&str) -> bool| {
+            word_spans(text)
+                .into_iter()
+                .filter(|&(start, end)| accepts(&text[start..end].to_lowercase()))
+                .collect()
+        };
+        match self {
+            Matcher::Substring(_, re) | Matcher::Word(_, re) | Matcher::Regex(re) => re
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+            Matcher::Stem(stems, whole_words) => words_where(&|word| {
+                let word = stem(word);
+                stems.iter().any(|s| word_matches(&word, s, *whole_words))
+            }),
+            Matcher::Fuzzy(terms) => words_where(&|word| {
+                terms
+                    .iter()
+                    .any(|term| fuzzy_distance(word, term).is_some())
+            }),
+            Matcher::Query { query, stemmed } => {
+                let terms = query.terms();
+                words_where(&|word| {
+                    let word = if *stemmed {
+                        stem(word)
+                    } else {
+                        word.to_string()
+                    };
+                    terms.contains(&word.as_str())
+                })
+            }
+        }
+    }
+
+    /// Positions of the verses that can match, from the search index. `None`
+    /// means scan every verse: raw regexes can't use the index, and neither can
+    /// queries without word characters.
+    fn candidates(&self, index: &SearchIndex) -> Option<Vec<usize>> {
+        // Every verse holding a word with this stem.
+        let by_stem = |term: &str, whole_words: bool| {
+            index.positions_where(|word| word_matches(&stem(word), term, whole_words))
+        };
+        match self {
+            Matcher::Substring(needle, _) => index.candidates(needle, false),
+            Matcher::Word(needle, _) => index.candidates(needle, true),
+            Matcher::Regex(_) => None,
+            Matcher::Stem(stems, whole_words) => stems
+                .iter()
+                .map(|term| by_stem(term, *whole_words))
+                .reduce(|a, b| intersect(&a, &b)),
+            Matcher::Fuzzy(terms) => terms
+                .iter()
+                .map(|term| index.positions_where(|word| fuzzy_distance(word, term).is_some()))
+                .reduce(|a, b| intersect(&a, &b)),
+            Matcher::Query { query, stemmed } => query.candidates(&|phrase: &[String]| {
+                if *stemmed {
+                    phrase
+                        .iter()
+                        .map(|term| by_stem(term, true))
+                        .reduce(|a, b| intersect(&a, &b))
+                } else {
+                    index.candidates(&phrase.join(" "), true)
+                }
+            }),
+        }
+    }
+
+    /// The terms a match is scored on for `--sort relevance`: each query word
+    /// (matched the way the query matches), or the whole pattern for a regex.
+    /// Verse counts come from the index when there is one.
+    fn score_terms<'a>(&'a self, index: Option<&SearchIndex>, corpus: &[Verse]) -> Vec<Term<'a>> {
+        // Each term with the test a verse word must pass to count for it.
+        let (mut terms, accepts_term): (Vec<String>, WordTest) = match self {
+            Matcher::Substring(needle, _) => (words(needle), |word, term| word.contains(term)),
+            Matcher::Word(needle, _) => (words(needle), |word, term| word == term),
+            Matcher::Stem(stems, true) => (stems.clone(), |word, term| stem(word) == term),
+            Matcher::Stem(stems, false) => (stems.clone(), |word, term| stem(word).contains(term)),
+            Matcher::Fuzzy(terms) => (terms.clone(), |word, term| {
+                fuzzy_distance(word, term).is_some()
+            }),
+            Matcher::Query { query, stemmed } => (
+                query.terms().into_iter().map(str::to_string).collect(),
+                if *stemmed {
+                    |word, term| stem(word) == term
+                } else {
+                    |word, term| word == term
+                },
+            ),
+            Matcher::Regex(re) => {
+                return vec![Term {
+                    frequency: Box::new(move |text: &str| re.find_iter(text).count()),
+                    verses_with: corpus.iter().filter(|v| re.is_match(&v.text)).count(),
+                }];
+            }
+        };
+        terms.sort();
+        terms.dedup();
+        terms
+            .into_iter()
+            .map(|term| {
+                let accepts = move |word: &str| accepts_term(word, &term);
+                let verses_with = match index {
+                    Some(index) => index.positions_where(&accepts).len(),
+                    None => corpus
+                        .iter()
+                        .filter(|v| words(&v.text).iter().any(|w| accepts(w)))
+                        .count(),
+                };
+                Term {
+                    frequency: Box::new(move |text: &str| {
+                        words(text).iter().filter(|w| accepts(w)).count()
+                    }),
+                    verses_with,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether a verse word counts as an occurrence of a query term.
+type WordTest = fn(&str, &str) -> bool;
+
+/// A verse word against a query term: equal, or containing it for substring
+/// queries.
+fn word_matches(word: &str, term: &str, whole_words: bool) -> bool {
+    if whole_words {
+        word == term
+    } else {
+        word.contains(term)
+    }
+}
+
+/// How many edits a `--fuzzy` query word tolerates: none up to two letters, one
+/// up to five, two up to nine, and three for longer names.
+fn edit_budget(term: &str) -> usize {
+    match term.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        6..=9 => 2,
+        _ => 3,
+    }
+}
+
+/// Edits between a verse word and a `--fuzzy` query word, if within budget.
+fn fuzzy_distance(word: &str, term: &str) -> Option<usize> {
+    let budget = edit_budget(term);
+    if word.chars().count().abs_diff(term.chars().count()) > budget {
+        return None;
+    }
+    Some(edit_distance(word, term)).filter(|&edits| edits <= budget)
+}
+
+/// The stems of a text's words, in order.
+fn stems_of(text: &str) -> Vec<String> {
+    words(text).iter().map(|w| stem(w)).collect()
+}
+
+/// Every verse's word stems, and TF-IDF weights over them, for comparing
+/// wording (`similar`, `whereis`).
+fn stem_weights(verses: &[Verse]) -> (Vec<Vec<String>>, TfIdf) {
+    let docs: Vec<Vec<String>> = verses.iter().map(|v| stems_of(&v.text)).collect();
+    let tfidf = TfIdf::new(&docs);
+    (docs, tfidf)
+}
+
+/// The translation's search index, if it was built for the current manifest;
+/// `None` for raw regexes, which can't use it.
+fn search_index(matcher: &Matcher, paths: &CachePaths) -> Option<SearchIndex> {
+    if let Matcher::Regex(_) = matcher {
+        return None;
+    }
+    let manifest = read_manifest(&paths.manifest_path())?;
+    index::open_current(
+        &paths.index_path(),
+        &manifest.created_at,
+        manifest.verse_count,
+    )
+}
+
+/// The verses a search looks through. When the index can narrow the query,
+/// only its candidates are decoded, unless the search needs the `whole`
+/// translation; then the candidates come back as positions among all verses.
+fn search_verses(
+    matcher: &Matcher,
+    index: Option<&SearchIndex>,
+    paths: &CachePaths,
+    whole: bool,
+) -> Result<(Vec<Verse>, Option<Vec<usize>>)> {
+    let candidates = index.and_then(|i| matcher.candidates(i));
+    let path = paths.verses_path();
+    if let Some(positions) = candidates.as_deref().filter(|_| !whole) {
+        let verses = load_positions(&path, positions)
+            .with_context(|| missing_cache_msg(&paths.translation))?;
+        return Ok((verses, None));
+    }
+    let verses = load_verses(&path).with_context(|| missing_cache_msg(&paths.translation))?;
+    Ok((verses, candidates))
 }
 
 fn build_matcher(args: &SearchArgs) -> Result<Matcher> {
-    if args.regex || args.word {
+    if args.boolean {
+        let mut query = parse_query(&args.query)?;
+        if args.stem {
+            query = query.stemmed();
+        }
+        return Ok(Matcher::Query {
+            query,
+            stemmed: args.stem,
+        });
+    }
+    if args.fuzzy {
+        let terms = words(&args.query);
+        if terms.is_empty() {
+            bail!("--fuzzy needs a query with words in it");
+        }
+        return Ok(Matcher::Fuzzy(terms));
+    }
+    if args.stem {
+        let stems: Vec<String> = words(&args.query).iter().map(|w| stem(w)).collect();
+        if stems.is_empty() {
+            bail!("--stem needs a query with words in it");
+        }
+        return Ok(Matcher::Stem(stems, args.word));
+    }
+    if args.regex {
+        // With --word the regex must match whole words.
         let pattern = if args.word {
-            // Whole-word match; the query is escaped unless it is already a regex.
-            let inner = if args.regex {
-                args.query.clone()
-            } else {
-                regex::escape(&args.query)
-            };
-            format!(r"\b(?:{})\b", inner)
+            format!(r"\b(?:{})\b", args.query)
         } else {
             args.query.clone()
         };
@@ -228,16 +1054,77 @@ fn build_matcher(args: &SearchArgs) -> Result<Matcher> {
             .with_context(|| format!("Invalid regex: {}", args.query))?;
         Ok(Matcher::Regex(re))
     } else {
-        Ok(Matcher::Substring(args.query.to_lowercase()))
+        literal_matcher(&args.query, args.word)
     }
 }
 
-pub fn run_today(args: &TodayArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
-    let verses =
-        load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
+/// A case-insensitive literal query, matched within words or as whole words.
+fn literal_matcher(query: &str, whole_words: bool) -> Result<Matcher> {
+    let escaped = regex::escape(query);
+    let pattern = if whole_words {
+        format!(r"\b(?:{})\b", escaped)
+    } else {
+        escaped
+    };
+    let re = RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .with_context(|| format!("Invalid query: {}", query))?;
+    Ok(if whole_words {
+        Matcher::Word(query.to_lowercase(), re)
+    } else {
+        Matcher::Substring(query.to_lowercase(), re)
+    })
+}
+
+/// `concordance`: every occurrence of a word, one line each, grouped by book in
+/// Bible order.
+pub fn run_concordance(
+    args: &ConcordanceArgs,
+    paths: &CachePaths,
+    output: &OutputStyle,
+) -> Result<()> {
+    let matcher = literal_matcher(&args.term, args.word)?;
+    let scope = Scope::new(args.book.as_deref(), None, None)?;
+    let index = search_index(&matcher, paths);
+    let (verses, _) = search_verses(&matcher, index.as_ref(), paths, false)?;
+    let matches = matching_verses(&matcher, None, &verses, &scope);
+    let books = occurrences_by_book(&matcher, matches);
+
+    if books.is_empty() {
+        if !output.is_structured() {
+            println!("No matches found.");
+        }
+        return Ok(());
+    }
+    output.emit_concordance(&books, args.width);
+    Ok(())
+}
 
-    let book_filter = normalize_book_filter(args.book.as_deref())?;
-    let pool = filter_verses(&verses, book_filter.as_deref(), args.testament);
+/// Every occurrence of the matcher's word in `matches`, in Bible order and
+/// grouped by book.
+fn occurrences_by_book<'a>(
+    matcher: &Matcher,
+    mut matches: Vec<&'a Verse>,
+) -> Vec<(&'a str, Vec<Occurrence<'a>>)> {
+    matches.sort_by_key(|v| canonical_order(v));
+    let mut books: Vec<(&str, Vec<Occurrence>)> = Vec::new();
+    for verse in matches {
+        for (start, end) in matcher.spans(&verse.text) {
+            let occurrence = Occurrence { verse, start, end };
+            match books.last_mut() {
+                Some((book, list)) if *book == verse.book => list.push(occurrence),
+                _ => books.push((&verse.book, vec![occurrence])),
+            }
+        }
+    }
+    books
+}
+
+pub fn run_today(args: &TodayArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
+    let scope = Scope::new(args.book.as_deref(), args.testament, args.within.as_deref())?;
+    let verses = scoped_verses(&scope, paths)?;
+    let pool: Vec<&Verse> = verses.iter().filter(|v| scope.contains(v)).collect();
     if pool.is_empty() {
         bail!("No verses match those constraints.");
     }
@@ -254,11 +1141,9 @@ pub fn run_today(args: &TodayArgs, paths: &CachePaths, output: &OutputStyle) ->
 }
 
 pub fn run_random(args: &RandomArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
-    let verses =
-        load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
-
-    let book_filter = normalize_book_filter(args.book.as_deref())?;
-    let mut pool = filter_verses(&verses, book_filter.as_deref(), args.testament);
+    let scope = Scope::new(args.book.as_deref(), args.testament, args.within.as_deref())?;
+    let verses = scoped_verses(&scope, paths)?;
+    let mut pool: Vec<&Verse> = verses.iter().filter(|v| scope.contains(v)).collect();
     if let Some(max) = args.max_words {
         pool.retain(|v| v.text.split_whitespace().count() <= max);
     }
@@ -279,37 +1164,10 @@ pub fn run_random(args: &RandomArgs, paths: &CachePaths, output: &OutputStyle) -
     Ok(())
 }
 
-/// Normalize an optional `--book` argument to its canonical name, erroring on
-/// an unknown book.
-fn normalize_book_filter(book: Option<&str>) -> Result<Option<String>> {
-    match book {
-        Some(book) => {
-            let normalized =
-                normalize_book(book).ok_or_else(|| anyhow::anyhow!("Unknown book: {}", book))?;
-            Ok(Some(normalized.to_string()))
-        }
-        None => Ok(None),
-    }
-}
-
-/// Filter the verse list by an optional book and/or testament.
-fn filter_verses<'a>(
-    verses: &'a [Verse],
-    book: Option<&str>,
-    testament: Option<Testament>,
-) -> Vec<&'a Verse> {
-    verses
-        .iter()
-        .filter(|v| match book {
-            Some(b) => v.book == b,
-            None => true,
-        })
-        .filter(|v| match testament {
-            Some(Testament::Ot) => is_old_testament(&v.book) == Some(true),
-            Some(Testament::Nt) => is_old_testament(&v.book) == Some(false),
-            None => true,
-        })
-        .collect()
+/// The active translation's verses, loading only the books `scope` can reach.
+fn scoped_verses(scope: &Scope, paths: &CachePaths) -> Result<Vec<Verse>> {
+    load_books(&paths.verses_path(), |book| scope.touches_book(book))
+        .with_context(|| missing_cache_msg(&paths.translation))
 }
 
 pub fn run_echo(args: &EchoArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
@@ -323,24 +1181,16 @@ pub fn run_echo(args: &EchoArgs, paths: &CachePaths, output: &OutputStyle) -> Re
 
     let verses =
         load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
+    let index = VerseIndex::build(&verses);
 
-    let mut chapter_verses: Vec<&Verse> = verses
-        .iter()
-        .filter(|v| v.book == reference.book && v.chapter == chapter)
-        .collect();
-    if chapter_verses.is_empty() {
-        bail!("No verses found for {} {}", reference.book, chapter);
-    }
-    chapter_verses.sort_by_key(|v| v.verse);
-
+    let anchor = format!("{} {}:{}", reference.book, chapter, verse_number);
+    let chapter_verses = index.chapter(&reference.book, chapter);
     let position = chapter_verses
         .iter()
         .position(|v| v.verse == verse_number)
-        .ok_or_else(|| anyhow::anyhow!("Verse not found"))?;
+        .ok_or_else(|| index.not_found(&anchor, &reference.book, Some(chapter)))?;
 
-    let window = args.window as usize;
-    let start = position.saturating_sub(window);
-    let end = (position + window).min(chapter_verses.len() - 1);
+    let (start, end) = context_window(chapter_verses.len(), position, args.window as usize);
 
     if output.is_structured() {
         let slice: Vec<&Verse> = chapter_verses[start..=end].to_vec();
@@ -633,12 +1483,17 @@ async fn run_ai_chat_streaming(
     Ok(())
 }
 
-fn print_book_overview(verses: &[Verse], reference: &ReferenceQuery) -> Result<()> {
+fn print_book_overview(
+    verses: &[Verse],
+    reference: &ReferenceQuery,
+    output: &OutputStyle,
+) -> Result<()> {
     let Some(max_chapter) = max_chapter(verses, &reference.book) else {
         bail!("Book not found: {}", reference.book);
     };
-    println!("{} has {} chapters.", reference.book, max_chapter);
-    println!("Tip: bible read {} <chapter>", reference.book);
+    let book = output.book_name(&reference.book);
+    println!("{} has {} chapters.", book, max_chapter);
+    println!("Tip: bible read {} <chapter>", book);
     Ok(())
 }
 
@@ -658,18 +1513,21 @@ fn select_ai_verses<'a>(
     reference: &ReferenceQuery,
     window: u16,
 ) -> Result<Vec<&'a Verse>> {
+    let index = VerseIndex::build(verses);
+    // A range across chapters or books is sent as-is; the window only widens
+    // around a single anchor verse.
+    if reference.end.is_some() {
+        return index.resolve(reference);
+    }
+
     let chapter = reference
         .chapter
         .ok_or_else(|| anyhow::anyhow!("Chapter is required for AI prompts"))?;
 
-    let mut chapter_verses: Vec<&Verse> = verses
-        .iter()
-        .filter(|v| v.book == reference.book && v.chapter == chapter)
-        .collect();
+    let chapter_verses = index.chapter(&reference.book, chapter);
     if chapter_verses.is_empty() {
-        bail!("No verses found for {} {}", reference.book, chapter);
+        return Err(index.not_found(reference, &reference.book, Some(chapter)));
     }
-    chapter_verses.sort_by_key(|v| v.verse);
 
     let Some(verse_number) = reference.verse else {
         return Ok(chapter_verses);
@@ -678,7 +1536,7 @@ fn select_ai_verses<'a>(
     let position = chapter_verses
         .iter()
         .position(|v| v.verse == verse_number)
-        .ok_or_else(|| anyhow::anyhow!("Verse not found"))?;
+        .ok_or_else(|| index.not_found(reference, &reference.book, Some(chapter)))?;
 
     let window = window as usize;
     let start = position.saturating_sub(window);
@@ -750,7 +1608,7 @@ fn resolve_selection<'a>(
     verses: &'a [Verse],
     reference: &ReferenceQuery,
 ) -> Result<Vec<&'a Verse>> {
-    if reference.chapter.is_none() {
+    if reference.is_whole_book() {
         let bv = book_verses(verses, &reference.book);
         if bv.is_empty() {
             bail!("Book not found: {}", reference.book);
@@ -761,25 +1619,33 @@ fn resolve_selection<'a>(
     }
 }
 
-/// A human label for a contiguous selection, e.g. `John 3:16` or `John 3:16-18`.
-fn passage_label(selected: &[&Verse]) -> String {
-    match (selected.first(), selected.last()) {
-        (Some(first), Some(last)) if selected.len() > 1 => {
-            if first.chapter == last.chapter {
-                format!(
-                    "{} {}:{}-{}",
-                    first.book, first.chapter, first.verse, last.verse
-                )
-            } else {
-                format!(
-                    "{} {}:{}-{}:{}",
-                    first.book, first.chapter, first.verse, last.chapter, last.verse
-                )
-            }
-        }
-        (Some(first), _) => format!("{} {}:{}", first.book, first.chapter, first.verse),
-        _ => String::new(),
+/// A label for a contiguous selection in the active reference style, e.g. `John 3:16`,
+/// `John 3:16-18`, or `Genesis 50:26-Exodus 1:2`.
+fn passage_label(selected: &[&Verse], output: &OutputStyle) -> String {
+    let (Some(first), Some(last)) = (selected.first(), selected.last()) else {
+        return String::new();
+    };
+    let mut query = ReferenceQuery {
+        book: first.book.clone(),
+        chapter: Some(first.chapter),
+        verse: Some(first.verse),
+        verse_end: None,
+        verse_list: Vec::new(),
+        end: None,
+        verse_part: None,
+        verse_end_part: None,
+        following: false,
+    };
+    if first.book == last.book && first.chapter == last.chapter {
+        query.verse_end = (last.verse != first.verse).then_some(last.verse);
+    } else {
+        query.end = Some(RangeEnd {
+            book: last.book.clone(),
+            chapter: Some(last.chapter),
+            verse: Some(last.verse),
+        });
     }
+    output.passage(&query)
 }
 
 pub fn run_topic(args: &TopicArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
@@ -796,7 +1662,7 @@ pub fn run_topic(args: &TopicArgs, paths: &CachePaths, output: &OutputStyle) ->
 
     if args.refs_only {
         for r in topic.refs {
-            println!("{} {}:{}", r.book, r.chapter, r.verse);
+            println!("{}", output.verse_reference(r.book, r.chapter, r.verse));
         }
         return Ok(());
     }
@@ -823,18 +1689,20 @@ pub fn run_export(args: &ExportArgs, paths: &CachePaths, output: &OutputStyle) -
         load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
     let index = VerseIndex::build(&verses);
     let selected = resolve_selection(&index, &verses, &reference)?;
-    let _ = output; // export format is controlled by --to, not the global format
 
+    // The export format is controlled by --to, not the global format; only the
+    // reference style carries over, through `passage_label` and
+    // `output.reference`.
     match args.to {
         ExportTarget::Md => {
             println!(
                 "## {} ({})",
-                passage_label(&selected),
+                passage_label(&selected, output),
                 paths.translation.to_uppercase()
             );
             println!();
             for v in &selected {
-                println!("**{} {}:{}** {}", v.book, v.chapter, v.verse, v.text);
+                println!("**{}** {}", output.reference(v), v.text);
                 println!();
             }
         }
@@ -842,11 +1710,11 @@ pub fn run_export(args: &ExportArgs, paths: &CachePaths, output: &OutputStyle) -
             for v in &selected {
                 // front<TAB>back; tabs/newlines in text are unlikely but stripped.
                 let text = v.text.replace(['\t', '\n'], " ");
-                println!("{} {}:{}\t{}", v.book, v.chapter, v.verse, text);
+                println!("{}\t{}", output.reference(v), text);
             }
         }
         ExportTarget::Json => {
-            println!("{}", crate::output::verses_to_json(&selected));
+            println!("{}", crate::output::verses_to_json(&selected, output));
         }
         ExportTarget::Txt => {
             for v in &selected {
@@ -902,7 +1770,7 @@ pub fn run_parallel(args: &ParallelArgs, paths: &CachePaths, output: &OutputStyl
             );
             obj.insert(
                 "reference".into(),
-                serde_json::Value::String(format!("{} {}:{}", v.book, v.chapter, v.verse)),
+                serde_json::Value::String(output.reference(v)),
             );
             let mut tx = serde_json::Map::new();
             for (i, id) in ids.iter().enumerate() {
@@ -933,8 +1801,7 @@ pub fn run_parallel(args: &ParallelArgs, paths: &CachePaths, output: &OutputStyl
         if n > 0 {
             println!();
         }
-        let reference = format!("{} {}:{}", v.book, v.chapter, v.verse);
-        output.print_reference_heading(&reference);
+        output.print_reference_heading(&output.reference(v));
         for (i, id) in ids.iter().enumerate() {
             let text = indexes[i]
                 .get(&v.book, v.chapter, v.verse)
@@ -958,15 +1825,30 @@ pub fn run_translation(args: &TranslationArgs, paths: &CachePaths) -> Result<()>
                 let marker = if t.id == paths.translation { "*" } else { " " };
                 let detail = t
                     .manifest
-                    .map(|m| format!("{} verses", m.verse_count))
+                    .map(|m| {
+                        let mut detail = format!("{} verses", m.verse_count);
+                        for extra in [m.language, m.title].into_iter().flatten() {
+                            detail.push_str(", ");
+                            detail.push_str(&extra);
+                        }
+                        detail
+                    })
                     .unwrap_or_default();
                 println!("{} {:<6} {}", marker, t.id, detail);
             }
             Ok(())
         }
         TranslationAction::Add(a) => {
-            let count = preload(paths, &a.id, a.source.as_deref())?;
+            let count = preload(
+                paths,
+                &a.id,
+                a.source.as_deref(),
+                a.language.as_deref(),
+                a.source_format,
+                a.columns.as_deref(),
+            )?;
             println!("{} installed: {} verses", a.id.to_uppercase(), count);
+            warn_integrity(paths, &a.id);
             Ok(())
         }
         TranslationAction::Default(a) => {
@@ -989,7 +1871,201 @@ pub fn run_translation(args: &TranslationArgs, paths: &CachePaths) -> Result<()>
             }
             Ok(())
         }
+        TranslationAction::Verify(a) => {
+            let id = a.id.as_deref().unwrap_or(&paths.translation);
+            if !paths.is_installed(id) {
+                bail!(
+                    "{} is not installed. Run `bible translation add {}` first.",
+                    id.to_uppercase(),
+                    id
+                );
+            }
+            let verses = load_verses(&paths.verses_path_for(id))?;
+            let (notes, problems): (Vec<Issue>, Vec<Issue>) = verify(&verses)
+                .into_iter()
+                .partition(Issue::is_versification);
+            if problems.is_empty() {
+                println!(
+                    "{}: {} verses, no problems found",
+                    id.to_uppercase(),
+                    verses.len()
+                );
+            } else {
+                println!("{}: {} verses", id.to_uppercase(), verses.len());
+            }
+            for issue in &problems {
+                println!("  {}", issue);
+            }
+            for note in &notes {
+                println!("  note: {} (may be versification)", note);
+            }
+            println!("  note: missing final verses of a chapter aren't detected, as verse counts vary between versifications");
+            if problems.is_empty() {
+                return Ok(());
+            }
+            bail!(
+                "{} problem{} found in {}",
+                problems.len(),
+                if problems.len() == 1 { "" } else { "s" },
+                id.to_uppercase()
+            )
+        }
+    }
+}
+
+/// Most problems listed after an install; `translation verify` lists them all.
+const INSTALL_WARNINGS: usize = 10;
+
+/// Check a freshly installed translation and warn about what looks wrong,
+/// without failing the install.
+fn warn_integrity(paths: &CachePaths, id: &str) {
+    let Ok(verses) = load_verses(&paths.verses_path_for(id)) else {
+        return;
+    };
+    let issues: Vec<Issue> = verify(&verses)
+        .into_iter()
+        .filter(|issue| !issue.is_versification())
+        .collect();
+    for issue in issues.iter().take(INSTALL_WARNINGS) {
+        eprintln!("warning: {}", issue);
+    }
+    if issues.len() > INSTALL_WARNINGS {
+        eprintln!(
+            "warning: ...and {} more (run `bible translation verify {}`)",
+            issues.len() - INSTALL_WARNINGS,
+            id
+        );
+    }
+}
+
+pub fn run_refs(args: &RefsArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
+    match &args.action {
+        RefsAction::Scan(a) => run_refs_scan(a, paths, output),
+        RefsAction::Format(a) => run_refs_format(a, output),
+    }
+}
+
+fn run_refs_format(args: &RefsFormatArgs, output: &OutputStyle) -> Result<()> {
+    let style = args.style.unwrap_or(output.ref_style);
+    let references = parse_references(&args.reference)?;
+
+    if output.is_structured() {
+        let arr: Vec<serde_json::Value> = references
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "reference": r.styled_in(style, output.locale),
+                    "osis": r.styled(RefStyle::Osis),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&arr).unwrap_or_else(|_| "[]".to_string())
+        );
+        return Ok(());
+    }
+
+    let formatted: Vec<String> = references
+        .iter()
+        .map(|r| r.styled_in(style, output.locale))
+        .collect();
+    println!("{}", formatted.join("; "));
+    Ok(())
+}
+
+fn run_refs_scan(args: &RefsScanArgs, paths: &CachePaths, output: &OutputStyle) -> Result<()> {
+    let text = if args.input == "-" {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .context("Failed reading stdin")?;
+        buf
+    } else {
+        fs::read_to_string(&args.input).with_context(|| format!("Failed reading {}", args.input))?
+    };
+    let verses =
+        load_verses(&paths.verses_path()).with_context(|| missing_cache_msg(&paths.translation))?;
+    let index = VerseIndex::build(&verses);
+
+    // Each match with its normalized form and, if it does not resolve in the
+    // active translation, the reason why.
+    let checked: Vec<_> = find_references(&text)
+        .into_iter()
+        .map(|found| {
+            let (normalized, problem) = match &found.query {
+                Ok(q) => (
+                    output.passage(q),
+                    resolve_selection(&index, &verses, q)
+                        .err()
+                        .map(|e| e.to_string()),
+                ),
+                Err(e) => (found.text.clone(), Some(e.to_string())),
+            };
+            (found, normalized, problem)
+        })
+        .collect();
+    let invalid = checked.iter().filter(|(_, _, p)| p.is_some()).count();
+
+    if output.is_structured() {
+        let arr: Vec<serde_json::Value> = checked
+            .iter()
+            .map(|(found, normalized, problem)| {
+                serde_json::json!({
+                    "start": found.start,
+                    "end": found.end,
+                    "text": found.text,
+                    "reference": normalized,
+                    "valid": problem.is_none(),
+                    "error": problem,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&arr).unwrap_or_else(|_| "[]".to_string())
+        );
+    } else if checked.is_empty() {
+        println!("No references found.");
+    } else {
+        let span_width = checked
+            .iter()
+            .map(|(f, _, _)| format!("{}..{}", f.start, f.end).len())
+            .max()
+            .unwrap_or(0);
+        let ref_width = checked.iter().map(|(_, n, _)| n.len()).max().unwrap_or(0);
+        for (found, normalized, problem) in &checked {
+            let span = format!("{}..{}", found.start, found.end);
+            let status = match problem {
+                Some(p) => format!("invalid: {}", p),
+                None => "ok".to_string(),
+            };
+            println!(
+                "{:>sw$}  {:rw$}  {}",
+                span,
+                normalized,
+                status,
+                sw = span_width,
+                rw = ref_width
+            );
+        }
+        output.print_dim(&format!(
+            "{} reference{}, {} invalid",
+            checked.len(),
+            if checked.len() == 1 { "" } else { "s" },
+            invalid
+        ));
+    }
+
+    if args.check && invalid > 0 {
+        bail!(
+            "{} invalid reference{} in {}",
+            invalid,
+            if invalid == 1 { "" } else { "s" },
+            args.input
+        );
     }
+    Ok(())
 }
 
 pub fn run_tui(args: &TuiArgs, paths: &CachePaths) -> Result<()> {
@@ -1276,7 +2352,7 @@ pub fn run_diff(args: &DiffArgs, paths: &CachePaths, output: &OutputStyle) -> Re
     if ids.len() == 1 {
         ids.insert(0, paths.translation.clone());
     }
-    let mut seen = std::collections::HashSet::new();
+    let mut seen = HashSet::new();
     ids.retain(|id| seen.insert(id.clone()));
     if ids.len() < 2 {
         bail!("Provide at least two distinct translations, e.g. --with kjv,bbe");
@@ -1315,7 +2391,7 @@ pub fn run_diff(args: &DiffArgs, paths: &CachePaths, output: &OutputStyle) -> Re
             );
             obj.insert(
                 "reference".into(),
-                serde_json::Value::String(format!("{} {}:{}", v.book, v.chapter, v.verse)),
+                serde_json::Value::String(output.reference(v)),
             );
             obj.insert("base".into(), serde_json::Value::String(ids[0].clone()));
             let mut diffs = serde_json::Map::new();
@@ -1358,7 +2434,7 @@ pub fn run_diff(args: &DiffArgs, paths: &CachePaths, output: &OutputStyle) -> Re
         if n > 0 {
             println!();
         }
-        output.print_reference_heading(&format!("{} {}:{}", v.book, v.chapter, v.verse));
+        output.print_reference_heading(&output.reference(v));
 
         let base_tokens: Vec<&str> = v.text.split_whitespace().collect();
         let per_other: Vec<Option<Vec<DiffOp>>> = others
@@ -1443,6 +2519,84 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn facets_group_matches_by_book() {
+        let verses: Vec<Verse> = [("Genesis", 1), ("Genesis", 2), ("Exodus", 1), ("John", 3)]
+            .iter()
+            .map(|&(book, chapter)| Verse::new(book, chapter, 1, "text"))
+            .collect();
+        let matches: Vec<&Verse> = verses.iter().collect();
+        assert_eq!(
+            book_counts(&matches),
+            vec![("Genesis", 2), ("Exodus", 1), ("John", 1)]
+        );
+    }
+
+    #[test]
+    fn whereis_places_a_quote_across_two_verses() {
+        let verses: Vec<Verse> = [
+            ("Genesis", 1, 1, "In the beginning God created the heaven and the earth."),
+            ("Genesis", 1, 2, "And the earth was without form, and void; and darkness was upon the face of the deep."),
+            ("Genesis", 1, 3, "And God said, Let there be light: and there was light."),
+            ("John", 1, 1, "In the beginning was the Word, and the Word was with God, and the Word was God."),
+            ("John", 1, 5, "And the light shineth in darkness; and the darkness comprehended it not."),
+            ("Psalms", 23, 1, "The LORD is my shepherd; I shall not want."),
+        ]
+        .iter()
+        .map(|&(book, chapter, verse, text)| Verse::new(book, chapter, verse, text))
+        .collect();
+        let (docs, tfidf) = stem_weights(&verses);
+        let scope = Scope::new(None, None, None).unwrap();
+
+        let quote = stems_of("God made the heavens and the earth, and the earth was formless");
+        let found = quote_sources(&quote, &verses, &docs, &tfidf, &scope, 3).unwrap();
+        assert_eq!(found[0].positions, vec![0, 1]);
+        assert!(found[0].confidence > 0.6);
+
+        // Only words most verses share can't place a quotation.
+        assert!(quote_sources(&stems_of("and the"), &verses, &docs, &tfidf, &scope, 3).is_err());
+    }
+
+    #[test]
+    fn concordance_groups_occurrences_by_book_in_bible_order() {
+        let verses = [
+            Verse::new("John", 1, 5, "And the light shineth in darkness"),
+            Verse::new("Genesis", 1, 4, "And God saw the light, that it was good"),
+            Verse::new("Genesis", 1, 3, "Let there be light: and there was light."),
+            Verse::new("Psalms", 23, 1, "The LORD is my shepherd"),
+        ];
+        let matcher = literal_matcher("light", true).unwrap();
+        let scope = Scope::new(None, None, None).unwrap();
+        let matches = matching_verses(&matcher, None, &verses, &scope);
+        let books = occurrences_by_book(&matcher, matches);
+
+        let summary: Vec<(&str, Vec<String>)> = books
+            .iter()
+            .map(|(book, list)| {
+                let found = list
+                    .iter()
+                    .map(|o| format!("{}:{} {}", o.verse.chapter, o.verse.verse, o.start))
+                    .collect();
+                (*book, found)
+            })
+            .collect();
+        // Genesis 1:3 holds the word twice; each occurrence is its own line.
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Genesis",
+                    vec![
+                        "1:3 13".to_string(),
+                        "1:3 34".to_string(),
+                        "1:4 16".to_string()
+                    ]
+                ),
+                ("John", vec!["1:5 8".to_string()]),
+            ]
+        );
+    }
+
     #[test]
     fn diff_identical_text_is_all_equal() {
         let ops = ops_summary("For God so loved", "For God so loved");
@@ -1488,4 +2642,23 @@ mod tests {
         assert!(ops_summary("a b", "").iter().all(|(op, _)| *op == '-'));
         assert!(ops_summary("", "a b").iter().all(|(op, _)| *op == '+'));
     }
+
+    #[test]
+    fn fuzzy_words_tolerate_typos_by_length() {
+        assert_eq!(fuzzy_distance("nebuchadnezzar", "nebuchadnezar"), Some(1));
+        assert_eq!(fuzzy_distance("jerusalem", "jerusalm"), Some(1));
+        assert_eq!(fuzzy_distance("peter", "petr"), Some(1));
+        assert_eq!(fuzzy_distance("paul", "pual"), Some(1));
+        assert_eq!(fuzzy_distance("ye", "ya"), None);
+        assert_eq!(fuzzy_distance("moses", "jesus"), None);
+
+        let matcher = Matcher::Fuzzy(vec!["nebuchadnezar".to_string(), "king".to_string()]);
+        let text = "Then Nebuchadnezzar the king spake";
+        assert!(!matcher.is_match(text));
+        assert_eq!(matcher.distance("Nebuchadnezzar king of Babylon"), Some(1));
+        assert_eq!(
+            matcher.spans("Nebuchadnezzar king"),
+            vec![(0, 14), (15, 19)]
+        );
+    }
 }
@@ -0,0 +1,319 @@
+use anyhow::{bail, Context, Result};
+use memmap2::Mmap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::verses::Verse;
+
+/// Binary corpus layout, little-endian throughout:
+///
+/// - header: magic, format version, then book, chapter, and verse counts (u32)
+/// - books: name offset and length, first chapter row, chapter count (4 × u32)
+/// - chapters: number (u16, padded to u32), first verse row, verse count (3 × u32)
+/// - verses: number (u16, padded to u32), text offset and length (3 × u32)
+/// - strings: book names and verse texts as one UTF-8 blob
+///
+/// The book and chapter tables are the index: each row is a run of consecutive
+/// verses, so the stored order is kept exactly, and for a source in Bible order
+/// a passage is found by walking at most 66 books and their chapters.
+const MAGIC: &[u8; 8] = b"BIBLEBIN";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 24;
+const BOOK_LEN: usize = 16;
+const CHAPTER_LEN: usize = 12;
+const VERSE_LEN: usize = 12;
+
+/// Where the binary corpus for a JSONL cache file lives (`verses.bin`).
+pub fn binary_path(jsonl: &Path) -> PathBuf {
+    jsonl.with_extension("bin")
+}
+
+/// The binary corpus beside `jsonl`, if it exists, opens, and is at least as
+/// new as the JSONL it was built from.
+pub fn open_current(jsonl: &Path) -> Option<Corpus> {
+    let binary = binary_path(jsonl);
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    if modified(&binary)? < modified(jsonl)? {
+        return None;
+    }
+    Corpus::open(&binary).ok()
+}
+
+/// Write `verses` as a binary corpus, in the order given. The file is written
+/// beside `path` and renamed into place, so a reader never maps a half-written
+/// corpus.
+pub fn write(path: &Path, verses: &[Verse]) -> Result<()> {
+    let tmp = path.with_extension("bin.tmp");
+    fs::write(&tmp, encode(verses)?)
+        .with_context(|| format!("Failed writing {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed writing {}", path.display()))?;
+    Ok(())
+}
+
+/// A run of verses from one book, split into runs from one chapter.
+type BookRun<'a> = (&'a str, Vec<&'a [Verse]>);
+
+fn encode(verses: &[Verse]) -> Result<Vec<u8>> {
+    // A book or chapter that recurs out of order gets another run, never
+    // regrouping, so decoding gives back exactly the order stored.
+    let books: Vec<BookRun> = verses
+        .chunk_by(|a, b| a.book == b.book)
+        .map(|run| {
+            let chapters = run.chunk_by(|a, b| a.chapter == b.chapter).collect();
+            (run[0].book.as_str(), chapters)
+        })
+        .collect();
+
+    let len = |n: usize| u32::try_from(n).context("Corpus too large for the binary format");
+    let chapter_count: usize = books.iter().map(|(_, c)| c.len()).sum();
+    let mut book_rows = Vec::with_capacity(books.len() * BOOK_LEN);
+    let mut chapter_rows = Vec::with_capacity(chapter_count * CHAPTER_LEN);
+    let mut verse_rows = Vec::with_capacity(verses.len() * VERSE_LEN);
+    let mut strings = Vec::new();
+    let (mut chapter_row, mut verse_row) = (0, 0);
+    for (name, chapters) in &books {
+        for field in [
+            len(strings.len())?,
+            len(name.len())?,
+            chapter_row,
+            len(chapters.len())?,
+        ] {
+            book_rows.extend_from_slice(&field.to_le_bytes());
+        }
+        strings.extend_from_slice(name.as_bytes());
+        chapter_row += len(chapters.len())?;
+        for list in chapters {
+            for field in [u32::from(list[0].chapter), verse_row, len(list.len())?] {
+                chapter_rows.extend_from_slice(&field.to_le_bytes());
+            }
+            verse_row += len(list.len())?;
+            for v in *list {
+                for field in [u32::from(v.verse), len(strings.len())?, len(v.text.len())?] {
+                    verse_rows.extend_from_slice(&field.to_le_bytes());
+                }
+                strings.extend_from_slice(v.text.as_bytes());
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(
+        HEADER_LEN + book_rows.len() + chapter_rows.len() + verse_rows.len() + strings.len(),
+    );
+    out.extend_from_slice(MAGIC);
+    for field in [VERSION, len(books.len())?, chapter_row, verse_row] {
+        out.extend_from_slice(&field.to_le_bytes());
+    }
+    out.extend_from_slice(&book_rows);
+    out.extend_from_slice(&chapter_rows);
+    out.extend_from_slice(&verse_rows);
+    out.extend_from_slice(&strings);
+    Ok(out)
+}
+
+/// A binary corpus, memory-mapped and decoded on demand: only the books a
+/// caller asks for are turned into `Verse` values.
+pub struct Corpus<B = Mmap> {
+    bytes: B,
+    books: usize,
+    chapters: usize,
+    verses: usize,
+}
+
+impl Corpus {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed opening {}", path.display()))?;
+        // SAFETY: corpus files are only ever replaced by rename (see `write`),
+        // never modified in place, so the mapped bytes do not change under us.
+        let map = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("Failed mapping {}", path.display()))?;
+        Self::parse(map).with_context(|| format!("Invalid binary corpus at {}", path.display()))
+    }
+}
+
+impl<B: AsRef<[u8]>> Corpus<B> {
+    fn parse(bytes: B) -> Result<Self> {
+        let data = bytes.as_ref();
+        if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+            bail!("not a binary corpus");
+        }
+        let field = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap()) as usize;
+        if field(8) != VERSION as usize {
+            bail!("unsupported format version {}", field(8));
+        }
+        let (books, chapters, verses) = (field(12), field(16), field(20));
+        let tables = HEADER_LEN + books * BOOK_LEN + chapters * CHAPTER_LEN + verses * VERSE_LEN;
+        if verses == 0 || tables > data.len() {
+            bail!("truncated or empty");
+        }
+        Ok(Self {
+            bytes,
+            books,
+            chapters,
+            verses,
+        })
+    }
+
+    /// Decode the verses of every book `keep` accepts, in stored order.
+    pub fn load(&self, keep: impl Fn(&str) -> bool) -> Result<Vec<Verse>> {
+        let chapter_table = HEADER_LEN + self.books * BOOK_LEN;
+        let verse_table = chapter_table + self.chapters * CHAPTER_LEN;
+        let mut out = Vec::new();
+        for b in 0..self.books {
+            let row = HEADER_LEN + b * BOOK_LEN;
+            let name = self.string(self.field(row), self.field(row + 4))?;
+            if !keep(name) {
+                continue;
+            }
+            let (first_chapter, chapter_count) = (self.field(row + 8), self.field(row + 12));
+            if first_chapter + chapter_count > self.chapters {
+                bail!("Binary corpus is corrupt: {} chapters out of range", name);
+            }
+            for c in first_chapter..first_chapter + chapter_count {
+                let row = chapter_table + c * CHAPTER_LEN;
+                let chapter = self.field(row) as u16;
+                let (first_verse, verse_count) = (self.field(row + 4), self.field(row + 8));
+                if first_verse + verse_count > self.verses {
+                    bail!(
+                        "Binary corpus is corrupt: {} {} out of range",
+                        name,
+                        chapter
+                    );
+                }
+                for v in first_verse..first_verse + verse_count {
+                    let row = verse_table + v * VERSE_LEN;
+                    out.push(Verse {
+                        book: name.to_string(),
+                        chapter,
+                        verse: self.field(row) as u16,
+                        text: self
+                            .string(self.field(row + 4), self.field(row + 8))?
+                            .to_string(),
+                    });
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Decode the verses at `positions` (rows in stored order, as the search
+    /// index lists them), skipping any past the end. Each is found by binary
+    /// search over the chapter and book tables, so nothing else is decoded.
+    pub fn load_at(&self, positions: &[usize]) -> Result<Vec<Verse>> {
+        let chapter_table = HEADER_LEN + self.books * BOOK_LEN;
+        let verse_table = chapter_table + self.chapters * CHAPTER_LEN;
+        // The last row whose first entry (at `offset`) is at most `at`.
+        let containing = |table: usize, rows: usize, len: usize, offset: usize, at: usize| {
+            let (mut low, mut high) = (0, rows);
+            while low < high {
+                let mid = (low + high) / 2;
+                if self.field(table + mid * len + offset) <= at {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            low.checked_sub(1)
+        };
+        let mut out = Vec::with_capacity(positions.len());
+        for &position in positions.iter().filter(|&&p| p < self.verses) {
+            let corrupt = || anyhow::anyhow!("Binary corpus is corrupt: verse {}", position);
+            let c = containing(chapter_table, self.chapters, CHAPTER_LEN, 4, position)
+                .ok_or_else(corrupt)?;
+            let b = containing(HEADER_LEN, self.books, BOOK_LEN, 8, c).ok_or_else(corrupt)?;
+            let book_row = HEADER_LEN + b * BOOK_LEN;
+            let row = verse_table + position * VERSE_LEN;
+            out.push(Verse {
+                book: self
+                    .string(self.field(book_row), self.field(book_row + 4))?
+                    .to_string(),
+                chapter: self.field(chapter_table + c * CHAPTER_LEN) as u16,
+                verse: self.field(row) as u16,
+                text: self
+                    .string(self.field(row + 4), self.field(row + 8))?
+                    .to_string(),
+            });
+        }
+        Ok(out)
+    }
+
+    /// A u32 table field. Table rows were bounds-checked in `parse`.
+    fn field(&self, at: usize) -> usize {
+        let data = self.bytes.as_ref();
+        u32::from_le_bytes(data[at..at + 4].try_into().unwrap()) as usize
+    }
+
+    fn string(&self, offset: usize, len: usize) -> Result<&str> {
+        let start = HEADER_LEN
+            + self.books * BOOK_LEN
+            + self.chapters * CHAPTER_LEN
+            + self.verses * VERSE_LEN
+            + offset;
+        let bytes = self
+            .bytes
+            .as_ref()
+            .get(start..start + len)
+            .ok_or_else(|| anyhow::anyhow!("Binary corpus is corrupt: text out of range"))?;
+        std::str::from_utf8(bytes).context("Binary corpus is corrupt: invalid UTF-8")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(verses: &[Verse]) -> Vec<String> {
+        verses
+            .iter()
+            .map(|v| format!("{} {}:{} {}", v.book, v.chapter, v.verse, v.text))
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_and_decodes_only_kept_books() {
+        let verses = vec![
+            Verse::new("Genesis", 1, 1, "In the beginning"),
+            Verse::new("Genesis", 1, 2, "And the earth"),
+            Verse::new("Genesis", 2, 1, "Thus the heavens"),
+            Verse::new("John", 3, 16, "For God so loved the world"),
+            Verse::new("Revelation", 22, 21, "Amen. Ἀμήν"),
+        ];
+        let corpus = Corpus::parse(encode(&verses).unwrap()).unwrap();
+        assert_eq!(keys(&corpus.load(|_| true).unwrap()), keys(&verses));
+        assert_eq!(
+            keys(&corpus.load(|book| book == "John").unwrap()),
+            vec!["John 3:16 For God so loved the world"]
+        );
+
+        assert_eq!(
+            keys(&corpus.load_at(&[1, 3, 9]).unwrap()),
+            vec![
+                "Genesis 1:2 And the earth",
+                "John 3:16 For God so loved the world"
+            ]
+        );
+
+        // Out-of-order sources decode in the order they were stored.
+        let unsorted = vec![
+            Verse::new("John", 3, 16, "For God so loved the world"),
+            Verse::new("Genesis", 2, 1, "Thus the heavens"),
+            Verse::new("Genesis", 1, 1, "In the beginning"),
+            Verse::new("John", 1, 1, "In the beginning was the Word"),
+            Verse::new("Genesis", 2, 2, "And on the seventh day"),
+        ];
+        let corpus = Corpus::parse(encode(&unsorted).unwrap()).unwrap();
+        assert_eq!(keys(&corpus.load(|_| true).unwrap()), keys(&unsorted));
+        assert_eq!(
+            keys(&corpus.load_at(&[2, 3]).unwrap()),
+            keys(&unsorted[2..4])
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_or_truncated_bytes() {
+        assert!(Corpus::parse(b"{\"book\":\"John\"}".to_vec()).is_err());
+        let mut bytes = encode(&[Verse::new("John", 1, 1, "In the beginning")]).unwrap();
+        bytes.truncate(HEADER_LEN + 4);
+        assert!(Corpus::parse(bytes).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::{bail, Context, Result};
+
+use crate::books::{book_by_number, normalize_book};
+use crate::cache::Imported;
+use crate::verses::Verse;
+
+/// What a CSV/TSV column holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Book,
+    Chapter,
+    Verse,
+    Text,
+    /// A numeric verse id, `BBCCCVVV` (43003016 is John 3:16).
+    Id,
+    Skip,
+}
+
+fn column_named(name: &str) -> Option<Column> {
+    Some(match name.trim().to_lowercase().as_str() {
+        "book" | "b" | "book_name" | "bookname" | "book_id" => Column::Book,
+        "chapter" | "c" | "chapter_id" => Column::Chapter,
+        "verse" | "v" | "verse_num" | "verse_number" => Column::Verse,
+        "text" | "t" | "content" | "verse_text" | "scripture" => Column::Text,
+        "id" | "verse_id" | "vid" => Column::Id,
+        "-" | "_" | "" => Column::Skip,
+        _ => return None,
+    })
+}
+
+/// Whether a first line looks tab- or comma-separated: `Some(b'\t')`,
+/// `Some(b',')`, or `None`.
+pub fn detect_delimiter(raw: &str) -> Option<u8> {
+    let line = raw.lines().next().unwrap_or("");
+    if line.contains('\t') {
+        Some(b'\t')
+    } else if line.contains(',') {
+        Some(b',')
+    } else {
+        None
+    }
+}
+
+/// Parse CSV (`b','`) or TSV (`b'\t'`) rows into verses. `columns` names each
+/// column's role in order (`book,chapter,verse,text`, `id,text`, `-` to skip);
+/// without it the roles come from a header row, or from the usual column count:
+/// two are `id,text`, four `book,chapter,verse,text`, five
+/// `id,book,chapter,verse,text`. Books may be names or numbers (1-66). When
+/// text is the last column, extra fields are an unquoted delimiter in the text
+/// and are joined back into it; any other row of the wrong width is an error.
+pub fn parse(raw: &str, delimiter: u8, columns: Option<&str>) -> Result<Imported> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        // Tab-separated text is taken literally; quotes are part of the verse.
+        .quoting(delimiter != b'\t')
+        .from_reader(raw.as_bytes());
+    let mut rows = Vec::new();
+    for (at, record) in reader.records().enumerate() {
+        rows.push(record.with_context(|| format!("Invalid row {}", at + 1))?);
+    }
+    let Some(first) = rows.first() else {
+        bail!("No rows found in delimited source");
+    };
+
+    let header = header_columns(first);
+    let layout = match columns {
+        Some(spec) => parse_columns(spec)?,
+        None => match &header {
+            Some(layout) => layout.clone(),
+            None => match usual_width(&rows) {
+                2 => vec![Column::Id, Column::Text],
+                4 => vec![Column::Book, Column::Chapter, Column::Verse, Column::Text],
+                5 => vec![
+                    Column::Id,
+                    Column::Book,
+                    Column::Chapter,
+                    Column::Verse,
+                    Column::Text,
+                ],
+                n => bail!(
+                    "Can't tell what the {} columns hold; pass --columns (e.g. book,chapter,verse,text)",
+                    n
+                ),
+            },
+        },
+    };
+    let has = |column| layout.contains(&column);
+    if !has(Column::Text)
+        || !(has(Column::Id) || (has(Column::Book) && has(Column::Chapter) && has(Column::Verse)))
+    {
+        bail!("Columns need text plus book, chapter, and verse, or a verse id");
+    }
+
+    let text_last = layout.last() == Some(&Column::Text);
+    let separator = (delimiter as char).to_string();
+    let mut verses = Vec::new();
+    for (at, row) in rows.iter().enumerate() {
+        if at == 0 && header.is_some() {
+            continue;
+        }
+        if row.len() < layout.len() || (row.len() > layout.len() && !text_last) {
+            bail!(
+                "Row {} has {} fields but the columns are {}; quote text that contains the delimiter",
+                at + 1,
+                row.len(),
+                layout.len()
+            );
+        }
+        let field = |column| {
+            layout
+                .iter()
+                .position(|c| *c == column)
+                .and_then(|i| row.get(i))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let text = if text_last {
+            let fields: Vec<&str> = row.iter().skip(layout.len() - 1).collect();
+            fields.join(&separator).trim().to_string()
+        } else {
+            field(Column::Text).unwrap_or_default().to_string()
+        };
+        if text.is_empty() {
+            continue;
+        }
+        let Some((book, chapter, verse)) = locate(&field) else {
+            // A header row that `--columns` overrides.
+            if at == 0 && columns.is_some() {
+                continue;
+            }
+            bail!(
+                "Row {} has no usable book, chapter, and verse: {:?}",
+                at + 1,
+                row
+            );
+        };
+        verses.push(Verse {
+            book,
+            chapter,
+            verse,
+            text,
+        });
+    }
+    if verses.is_empty() {
+        bail!("No verses found in delimited source");
+    }
+    Ok(Imported {
+        verses,
+        ..Imported::default()
+    })
+}
+
+/// The most common number of fields per row (the narrower on a tie), so a row
+/// with a stray delimiter doesn't decide the layout.
+fn usual_width(rows: &[csv::StringRecord]) -> usize {
+    let mut counts: Vec<(usize, usize)> = Vec::new();
+    for row in rows {
+        match counts.iter_mut().find(|(width, _)| *width == row.len()) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((row.len(), 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(width, count)| (count, std::cmp::Reverse(width)))
+        .map_or(0, |(width, _)| width)
+}
+
+fn parse_columns(spec: &str) -> Result<Vec<Column>> {
+    spec.split(',')
+        .map(|name| {
+            column_named(name).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown column '{}' in --columns (use book, chapter, verse, text, id, or -)",
+                    name.trim()
+                )
+            })
+        })
+        .collect()
+}
+
+/// The column roles named by a header row, if the row is one: it names a text
+/// column and where the verse is.
+fn header_columns(row: &csv::StringRecord) -> Option<Vec<Column>> {
+    let named: Vec<Option<Column>> = row.iter().map(column_named).collect();
+    let has = |column| named.contains(&Some(column));
+    if has(Column::Text) && (has(Column::Id) || (has(Column::Chapter) && has(Column::Verse))) {
+        Some(
+            named
+                .into_iter()
+                .map(|c| c.unwrap_or(Column::Skip))
+                .collect(),
+        )
+    } else {
+        None
+    }
+}
+
+/// A row's book, chapter, and verse: from their own columns, or else decoded
+/// from a verse id.
+fn locate<'a>(field: &impl Fn(Column) -> Option<&'a str>) -> Option<(String, u16, u16)> {
+    if let (Some(book), Some(chapter), Some(verse)) = (
+        field(Column::Book),
+        field(Column::Chapter),
+        field(Column::Verse),
+    ) {
+        let book = match book.parse::<usize>() {
+            Ok(number) => book_by_number(number)?.to_string(),
+            Err(_) => normalize_book(book).unwrap_or(book).to_string(),
+        };
+        return Some((book, chapter.parse().ok()?, verse.parse().ok()?));
+    }
+    let id: u32 = field(Column::Id)?.parse().ok()?;
+    let book = book_by_number((id / 1_000_000) as usize)?;
+    Some((
+        book.to_string(),
+        (id / 1000 % 1000) as u16,
+        (id % 1000) as u16,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs(imported: &Imported) -> Vec<(&str, u16, u16)> {
+        imported
+            .verses
+            .iter()
+            .map(|v| (v.book.as_str(), v.chapter, v.verse))
+            .collect()
+    }
+
+    #[test]
+    fn reads_headers_ids_and_explicit_columns() {
+        // scrollmapper-style CSV, with a header and numbered books.
+        let csv = "id,b,c,v,t\n1001001,1,1,1,\"In the beginning, God created.\"\n43003016,43,3,16,For God so loved the world.\n";
+        let imported = parse(csv, b',', None).unwrap();
+        assert_eq!(refs(&imported), vec![("Genesis", 1, 1), ("John", 3, 16)]);
+        assert_eq!(imported.verses[0].text, "In the beginning, God created.");
+
+        // Headerless id<TAB>text, with a literal quote in the text.
+        let tsv = "43003016\tFor God so \"loved\" the world.\n62004008\tGod is love.\n";
+        let imported = parse(tsv, b'\t', None).unwrap();
+        assert_eq!(refs(&imported), vec![("John", 3, 16), ("1 John", 4, 8)]);
+        assert_eq!(imported.verses[0].text, "For God so \"loved\" the world.");
+
+        // Explicit columns, skipping one, under a header they override.
+        let tsv = "Ref\tLibro\tCap\tVers\tTexto\nx\tJuan\t3\t16\tPorque de tal manera amó Dios al mundo.\n";
+        let imported = parse(tsv, b'\t', Some("-,book,chapter,verse,text")).unwrap();
+        assert_eq!(refs(&imported), vec![("John", 3, 16)]);
+
+        assert!(parse("a,b,c\n1,2,3\n", b',', None).is_err());
+        assert!(parse_columns("book,chapter,page,text").is_err());
+    }
+
+    #[test]
+    fn keeps_unquoted_delimiters_in_trailing_text() {
+        // The stray comma is on the first row, so it must not set the layout.
+        let csv = "Genesis,1,2,And the earth, was void\nGenesis,1,3,And God said\nGenesis,1,4,And God saw\n";
+        let imported = parse(csv, b',', None).unwrap();
+        assert_eq!(
+            refs(&imported),
+            vec![("Genesis", 1, 2), ("Genesis", 1, 3), ("Genesis", 1, 4)]
+        );
+        assert_eq!(imported.verses[0].text, "And the earth, was void");
+
+        // With text before other columns, a wrong width can't be repaired.
+        let csv = "In the beginning,43003016\nAnd the earth, was void,43003017\n";
+        let error = parse(csv, b',', Some("text,id")).unwrap_err();
+        assert!(error.to_string().contains("Row 2 has 3 fields"));
+    }
+}
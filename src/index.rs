@@ -0,0 +1,410 @@
+use anyhow::{bail, Context, Result};
+use memmap2::Mmap;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::verses::Verse;
+
+/// Inverted index layout (`index.bin`), little-endian throughout:
+///
+/// - header: magic, format version, verse count, stamp length, and term count
+///   (u32)
+/// - stamp: the manifest `created_at` the index was built for
+/// - terms: term offset and length, postings offset and count (4 × u32), sorted
+///   by term
+/// - term strings as one UTF-8 blob, then every posting list as u32 verse
+///   positions
+///
+/// Positions index the verse list in stored order, as `load_verses` returns it;
+/// the stamp and verse count tell a stale index from a current one. Rows are
+/// only written by `write`, so opening checks the header alone and lookups
+/// treat anything out of range as absent.
+const MAGIC: &[u8; 8] = b"BIBLEIDX";
+const VERSION: u32 = 2;
+const HEADER_LEN: usize = 24;
+const TERM_LEN: usize = 16;
+
+/// Characters that make up a token. A subset of the regex `\w` class, so every
+/// `\b` a whole-word regex matches at is also a token boundary.
+fn is_word_char(c: char) -> bool {
+    c.is_alphabetic() || c.is_ascii_digit() || c == '_'
+}
+
+/// The lowercased words of `text`, in order: the tokens the index stores.
+pub fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c| !is_word_char(c))
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Byte ranges of the words of `text`, in order, split as `words` splits them.
+pub fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (at, c) in text.char_indices() {
+        match (is_word_char(c), start) {
+            (true, None) => start = Some(at),
+            (false, Some(from)) => {
+                spans.push((from, at));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(from) = start {
+        spans.push((from, text.len()));
+    }
+    spans
+}
+
+/// How many words `text` has, counted as `words` would split it.
+pub fn word_count(text: &str) -> usize {
+    text.split(|c| !is_word_char(c))
+        .filter(|t| !t.is_empty())
+        .count()
+}
+
+/// The distinct lowercased tokens of `text`.
+fn tokens(text: &str) -> Vec<String> {
+    let mut out = words(text);
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// Build and write the index for `verses`, renaming it into place like the
+/// binary corpus.
+pub fn write(path: &Path, stamp: &str, verses: &[Verse]) -> Result<()> {
+    let tmp = path.with_extension("bin.tmp");
+    fs::write(&tmp, encode(stamp, verses)?)
+        .with_context(|| format!("Failed writing {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed writing {}", path.display()))?;
+    Ok(())
+}
+
+fn encode(stamp: &str, verses: &[Verse]) -> Result<Vec<u8>> {
+    let len = |n: usize| u32::try_from(n).context("Corpus too large for the search index");
+    let mut postings: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    for (position, verse) in verses.iter().enumerate() {
+        let position = len(position)?;
+        for token in tokens(&verse.text) {
+            postings.entry(token).or_default().push(position);
+        }
+    }
+
+    let mut term_rows = Vec::with_capacity(postings.len() * TERM_LEN);
+    let mut strings = Vec::new();
+    let mut lists = Vec::new();
+    let mut posting_count = 0;
+    for (term, list) in &postings {
+        for field in [
+            len(strings.len())?,
+            len(term.len())?,
+            posting_count,
+            len(list.len())?,
+        ] {
+            term_rows.extend_from_slice(&field.to_le_bytes());
+        }
+        strings.extend_from_slice(term.as_bytes());
+        for position in list {
+            lists.extend_from_slice(&position.to_le_bytes());
+        }
+        posting_count += len(list.len())?;
+    }
+
+    let mut out = Vec::with_capacity(
+        HEADER_LEN + stamp.len() + term_rows.len() + strings.len() + lists.len(),
+    );
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&len(verses.len())?.to_le_bytes());
+    out.extend_from_slice(&len(stamp.len())?.to_le_bytes());
+    out.extend_from_slice(&len(postings.len())?.to_le_bytes());
+    out.extend_from_slice(stamp.as_bytes());
+    out.extend_from_slice(&term_rows);
+    out.extend_from_slice(&strings);
+    out.extend_from_slice(&lists);
+    Ok(out)
+}
+
+/// The index at `path`, if it opens and was built for this manifest stamp and
+/// verse count. Otherwise `None`, and callers scan every verse; installing
+/// the translation again rebuilds it.
+pub fn open_current(path: &Path, stamp: &str, verses: usize) -> Option<SearchIndex> {
+    SearchIndex::open(path)
+        .ok()
+        .filter(|index| index.is_current(stamp, verses))
+}
+
+/// How a piece of the query must sit within an indexed token.
+enum Piece<'a> {
+    Exact(&'a str),
+    Prefix(&'a str),
+    Suffix(&'a str),
+    Within(&'a str),
+}
+
+impl Piece<'_> {
+    fn accepts(&self, term: &str) -> bool {
+        match self {
+            Piece::Exact(p) => term == *p,
+            Piece::Prefix(p) => term.starts_with(p),
+            Piece::Suffix(p) => term.ends_with(p),
+            Piece::Within(p) => term.contains(p),
+        }
+    }
+}
+
+/// Split a lowercased query into the pieces each matching verse must contain.
+/// A piece followed by a separator in the query ends a token in the text, one
+/// preceded by a separator starts one; `whole_words` bounds both edges.
+fn pieces(needle: &str, whole_words: bool) -> Vec<Piece<'_>> {
+    let parts: Vec<&str> = needle.split(|c| !is_word_char(c)).collect();
+    let last = parts.len() - 1;
+    parts
+        .iter()
+        .enumerate()
+        .filter(|(_, part)| !part.is_empty())
+        .map(
+            |(i, part)| match (whole_words || i > 0, whole_words || i < last) {
+                (true, true) => Piece::Exact(part),
+                (true, false) => Piece::Prefix(part),
+                (false, true) => Piece::Suffix(part),
+                (false, false) => Piece::Within(part),
+            },
+        )
+        .collect()
+}
+
+/// A memory-mapped inverted index from lowercased tokens to verse positions.
+pub struct SearchIndex<B = Mmap> {
+    bytes: B,
+    verses: usize,
+    terms: usize,
+    stamp: usize,
+}
+
+impl SearchIndex {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed opening {}", path.display()))?;
+        // SAFETY: index files are only ever replaced by rename (see `write`),
+        // never modified in place, so the mapped bytes do not change under us.
+        let map = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("Failed mapping {}", path.display()))?;
+        Self::parse(map).with_context(|| format!("Invalid search index at {}", path.display()))
+    }
+}
+
+impl<B: AsRef<[u8]>> SearchIndex<B> {
+    fn parse(bytes: B) -> Result<Self> {
+        let data = bytes.as_ref();
+        if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+            bail!("not a search index");
+        }
+        let field = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap()) as usize;
+        if field(8) != VERSION as usize {
+            bail!("unsupported format version {}", field(8));
+        }
+        let index = Self {
+            verses: field(12),
+            stamp: field(16),
+            terms: field(20),
+            bytes,
+        };
+        if index.strings_start() > index.bytes.as_ref().len() {
+            bail!("truncated");
+        }
+        Ok(index)
+    }
+
+    /// Whether the index was built for this manifest stamp and verse count.
+    pub fn is_current(&self, stamp: &str, verses: usize) -> bool {
+        let built_for = &self.bytes.as_ref()[HEADER_LEN..HEADER_LEN + self.stamp];
+        built_for == stamp.as_bytes() && self.verses == verses
+    }
+
+    /// Positions of the verses that can match `needle` (already lowercased),
+    /// in ascending order: a superset of the real matches, to be confirmed by
+    /// the matcher. `None` when the index cannot narrow the search (the query
+    /// has no word characters), so every verse must be scanned.
+    pub fn candidates(&self, needle: &str, whole_words: bool) -> Option<Vec<usize>> {
+        pieces(needle, whole_words)
+            .iter()
+            .map(|piece| self.union(self.matching_terms(piece)))
+            .reduce(|a, b| intersect(&a, &b))
+    }
+
+    /// Positions of the verses holding any term `accepts` takes, ascending.
+    pub fn positions_where(&self, accepts: impl Fn(&str) -> bool) -> Vec<usize> {
+        self.union((0..self.terms).filter(|&t| accepts(self.term(t))).collect())
+    }
+
+    /// Every position listed under any of `terms`, ascending and distinct.
+    fn union(&self, terms: Vec<usize>) -> Vec<usize> {
+        let mut found: Vec<usize> = terms
+            .into_iter()
+            .flat_map(|t| self.positions(t))
+            .map(|p| p as usize)
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// Rows of the terms a piece accepts. Exact and prefix pieces binary-search
+    /// the sorted terms; suffix and infix pieces scan them.
+    fn matching_terms(&self, piece: &Piece) -> Vec<usize> {
+        match piece {
+            Piece::Exact(p) | Piece::Prefix(p) => {
+                let start = self.lower_bound(p);
+                (start..self.terms)
+                    .take_while(|&t| self.term(t).starts_with(p))
+                    .filter(|&t| piece.accepts(self.term(t)))
+                    .collect()
+            }
+            Piece::Suffix(_) | Piece::Within(_) => (0..self.terms)
+                .filter(|&t| piece.accepts(self.term(t)))
+                .collect(),
+        }
+    }
+
+    /// The first term row not less than `key`.
+    fn lower_bound(&self, key: &str) -> usize {
+        let (mut low, mut high) = (0, self.terms);
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.term(mid) < key {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    fn term(&self, t: usize) -> &str {
+        let row = self.term_row(t);
+        let start = self.strings_start() + self.field(row);
+        self.bytes
+            .as_ref()
+            .get(start..start + self.field(row + 4))
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn positions(&self, t: usize) -> impl Iterator<Item = u32> + '_ {
+        let row = self.term_row(t);
+        let start = self.lists_start() + self.field(row + 8) * 4;
+        let data = self
+            .bytes
+            .as_ref()
+            .get(start..start + self.field(row + 12) * 4)
+            .unwrap_or_default();
+        data.chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .filter(|&p| (p as usize) < self.verses)
+    }
+
+    fn field(&self, at: usize) -> usize {
+        let data = self.bytes.as_ref();
+        u32::from_le_bytes(data[at..at + 4].try_into().unwrap()) as usize
+    }
+
+    fn term_row(&self, t: usize) -> usize {
+        HEADER_LEN + self.stamp + t * TERM_LEN
+    }
+
+    fn strings_start(&self) -> usize {
+        self.term_row(self.terms)
+    }
+
+    /// Posting lists start after the last term's string.
+    fn lists_start(&self) -> usize {
+        if self.terms == 0 {
+            return self.strings_start();
+        }
+        let row = self.term_row(self.terms - 1);
+        self.strings_start() + self.field(row) + self.field(row + 4)
+    }
+}
+
+/// Positions present in both sorted lists.
+pub fn intersect<T: Ord + Copy>(a: &[T], b: &[T]) -> Vec<T> {
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corpus() -> Vec<Verse> {
+        [
+            "For God so loved the world",
+            "The LORD is my shepherd",
+            "God is love.",
+            "Beloved, let us love one another: for love is of God",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, text)| Verse::new("John", 1, i as u16 + 1, text))
+        .collect()
+    }
+
+    fn index(verses: &[Verse]) -> SearchIndex<Vec<u8>> {
+        SearchIndex::parse(encode("stamp", verses).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn narrows_substring_and_word_queries() {
+        let verses = corpus();
+        let index = index(&verses);
+        // Substring: any token containing "love" (loved, beloved, love).
+        assert_eq!(index.candidates("love", false), Some(vec![0, 2, 3]));
+        assert_eq!(index.candidates("love", true), Some(vec![2, 3]));
+        // Phrase pieces: "god" ends a token, "so" starts one.
+        assert_eq!(index.candidates("god so", false), Some(vec![0]));
+        assert_eq!(index.candidates("lord", true), Some(vec![1]));
+        assert_eq!(index.candidates("zion", false), Some(vec![]));
+        assert_eq!(index.candidates(", ", false), None);
+    }
+
+    #[test]
+    fn word_spans_split_like_words() {
+        let text = "Beloved, let us love — one another.";
+        let spans = word_spans(text);
+        let split: Vec<String> = spans
+            .iter()
+            .map(|&(start, end)| text[start..end].to_lowercase())
+            .collect();
+        assert_eq!(split, words(text));
+        assert_eq!(spans[0], (0, 7));
+        assert_eq!(spans.last(), Some(&(text.len() - 8, text.len() - 1)));
+    }
+
+    #[test]
+    fn detects_a_stale_index() {
+        let verses = corpus();
+        let index = index(&verses);
+        assert!(index.is_current("stamp", verses.len()));
+        assert!(!index.is_current("newer", verses.len()));
+        assert!(!index.is_current("stamp", verses.len() - 1));
+        assert!(SearchIndex::parse(b"BIBLEBIN".to_vec()).is_err());
+    }
+}
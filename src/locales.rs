@@ -0,0 +1,374 @@
+/// Book names in another language. A translation's manifest `language` selects
+/// one, so its references display in that language; every locale's names and
+/// aliases are also accepted as input (`Juan 3:16`, `1. Mose 1`).
+pub struct Locale {
+    /// ISO 639-1 code (e.g. "es").
+    pub code: &'static str,
+    /// Further codes that select this locale (ISO 639-2 bibliographic and
+    /// terminologic forms).
+    pub other_codes: &'static [&'static str],
+    /// Name and aliases of each book, in the same canonical order as `BOOKS`.
+    pub books: &'static [(&'static str, &'static [&'static str]); 66],
+}
+
+impl Locale {
+    /// This locale's name for a canonical book, or the canonical name for a book
+    /// outside `BOOKS`.
+    pub fn book_name<'a>(&self, canonical: &'a str) -> &'a str {
+        match crate::books::book_position(canonical) {
+            Some(i) => self.books[i].0,
+            None => canonical,
+        }
+    }
+}
+
+/// The locale for a language code, ignoring case and any region (`pt-BR`,
+/// `es_ES`). `None` for English or an unsupported language.
+pub fn find_locale(language: &str) -> Option<&'static Locale> {
+    let primary = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    LOCALES
+        .iter()
+        .find(|l| l.code == primary || l.other_codes.contains(&primary.as_str()))
+}
+
+pub const LOCALES: &[Locale] = &[
+    Locale {
+        code: "es",
+        other_codes: &["spa"],
+        books: &SPANISH,
+    },
+    Locale {
+        code: "de",
+        other_codes: &["deu", "ger"],
+        books: &GERMAN,
+    },
+    Locale {
+        code: "fr",
+        other_codes: &["fra", "fre"],
+        books: &FRENCH,
+    },
+    Locale {
+        code: "pt",
+        other_codes: &["por"],
+        books: &PORTUGUESE,
+    },
+];
+
+/// Reina-Valera names.
+const SPANISH: [(&str, &[&str]); 66] = [
+    ("Génesis", &["gn", "gén"]),
+    ("Éxodo", &["éx"]),
+    ("Levítico", &["lv"]),
+    ("Números", &["nm", "núm"]),
+    ("Deuteronomio", &["dt"]),
+    ("Josué", &["jos"]),
+    ("Jueces", &["jue"]),
+    ("Rut", &["rt"]),
+    ("1 Samuel", &["1 s"]),
+    ("2 Samuel", &["2 s"]),
+    ("1 Reyes", &["1 r", "1 re"]),
+    ("2 Reyes", &["2 r", "2 re"]),
+    ("1 Crónicas", &["1 cr", "1 crón"]),
+    ("2 Crónicas", &["2 cr", "2 crón"]),
+    ("Esdras", &["esd"]),
+    ("Nehemías", &["neh"]),
+    ("Ester", &["est"]),
+    ("Job", &[]),
+    ("Salmos", &["sal", "sl"]),
+    ("Proverbios", &["pr", "prov"]),
+    ("Eclesiastés", &["ec", "ecl"]),
+    ("Cantares", &["cnt", "cantar de los cantares"]),
+    ("Isaías", &["is"]),
+    ("Jeremías", &["jer"]),
+    ("Lamentaciones", &["lm"]),
+    ("Ezequiel", &["ez"]),
+    ("Daniel", &["dn"]),
+    ("Oseas", &["os"]),
+    ("Joel", &["jl"]),
+    ("Amós", &["am"]),
+    ("Abdías", &["abd"]),
+    ("Jonás", &["jon"]),
+    ("Miqueas", &["mi", "miq"]),
+    ("Nahúm", &["nah"]),
+    ("Habacuc", &["hab"]),
+    ("Sofonías", &["sof"]),
+    ("Hageo", &["hag"]),
+    ("Zacarías", &["zac"]),
+    ("Malaquías", &["mal"]),
+    ("Mateo", &["mt"]),
+    ("Marcos", &["mr"]),
+    ("Lucas", &["lc"]),
+    ("Juan", &["jn"]),
+    ("Hechos", &["hch", "hech"]),
+    ("Romanos", &["ro", "rom"]),
+    ("1 Corintios", &["1 co", "1 cor"]),
+    ("2 Corintios", &["2 co", "2 cor"]),
+    ("Gálatas", &["gá", "gal"]),
+    ("Efesios", &["ef"]),
+    ("Filipenses", &["fil", "flp"]),
+    ("Colosenses", &["col"]),
+    ("1 Tesalonicenses", &["1 ts", "1 tes"]),
+    ("2 Tesalonicenses", &["2 ts", "2 tes"]),
+    ("1 Timoteo", &["1 ti", "1 tim"]),
+    ("2 Timoteo", &["2 ti", "2 tim"]),
+    ("Tito", &["tit"]),
+    ("Filemón", &["flm"]),
+    ("Hebreos", &["he", "heb"]),
+    ("Santiago", &["stg", "sant"]),
+    ("1 Pedro", &["1 p", "1 pe"]),
+    ("2 Pedro", &["2 p", "2 pe"]),
+    ("1 Juan", &["1 jn"]),
+    ("2 Juan", &["2 jn"]),
+    ("3 Juan", &["3 jn"]),
+    ("Judas", &["jud"]),
+    ("Apocalipsis", &["ap", "apoc"]),
+];
+
+/// Luther names.
+const GERMAN: [(&str, &[&str]); 66] = [
+    ("1. Mose", &["1 mo", "1 mos"]),
+    ("2. Mose", &["2 mo", "2 mos"]),
+    ("3. Mose", &["3 mo", "3 mos", "levitikus"]),
+    ("4. Mose", &["4 mo", "4 mos", "numeri"]),
+    ("5. Mose", &["5 mo", "5 mos", "deuteronomium"]),
+    ("Josua", &["jos"]),
+    ("Richter", &["ri"]),
+    ("Rut", &[]),
+    ("1. Samuel", &["1 sam"]),
+    ("2. Samuel", &["2 sam"]),
+    ("1. Könige", &["1 kön", "1 kö"]),
+    ("2. Könige", &["2 kön", "2 kö"]),
+    ("1. Chronik", &["1 chr"]),
+    ("2. Chronik", &["2 chr"]),
+    ("Esra", &["esr"]),
+    ("Nehemia", &["neh"]),
+    ("Ester", &["est"]),
+    ("Hiob", &["hi", "ijob"]),
+    ("Psalmen", &["ps", "psalm"]),
+    ("Sprüche", &["spr", "sprichwörter"]),
+    ("Prediger", &["pred", "kohelet"]),
+    ("Hohelied", &["hld", "hoheslied"]),
+    ("Jesaja", &["jes"]),
+    ("Jeremia", &["jer"]),
+    ("Klagelieder", &["klgl"]),
+    ("Hesekiel", &["hes", "ezechiel"]),
+    ("Daniel", &["dan"]),
+    ("Hosea", &["hos"]),
+    ("Joel", &[]),
+    ("Amos", &[]),
+    ("Obadja", &["obd"]),
+    ("Jona", &[]),
+    ("Micha", &[]),
+    ("Nahum", &["nah"]),
+    ("Habakuk", &["hab"]),
+    ("Zefanja", &["zef"]),
+    ("Haggai", &["hag"]),
+    ("Sacharja", &["sach"]),
+    ("Maleachi", &["mal"]),
+    ("Matthäus", &["mt", "matth"]),
+    ("Markus", &["mk"]),
+    ("Lukas", &["lk"]),
+    ("Johannes", &["joh"]),
+    ("Apostelgeschichte", &["apg"]),
+    ("Römer", &["röm"]),
+    ("1. Korinther", &["1 kor"]),
+    ("2. Korinther", &["2 kor"]),
+    ("Galater", &["gal"]),
+    ("Epheser", &["eph"]),
+    ("Philipper", &["phil"]),
+    ("Kolosser", &["kol"]),
+    ("1. Thessalonicher", &["1 thess"]),
+    ("2. Thessalonicher", &["2 thess"]),
+    ("1. Timotheus", &["1 tim"]),
+    ("2. Timotheus", &["2 tim"]),
+    ("Titus", &["tit"]),
+    ("Philemon", &["phlm"]),
+    ("Hebräer", &["hebr"]),
+    ("Jakobus", &["jak"]),
+    ("1. Petrus", &["1 petr"]),
+    ("2. Petrus", &["2 petr"]),
+    ("1. Johannes", &["1 joh"]),
+    ("2. Johannes", &["2 joh"]),
+    ("3. Johannes", &["3 joh"]),
+    ("Judas", &["jud"]),
+    ("Offenbarung", &["offb"]),
+];
+
+/// Louis Segond names.
+const FRENCH: [(&str, &[&str]); 66] = [
+    ("Genèse", &["gn"]),
+    ("Exode", &["ex"]),
+    ("Lévitique", &["lv"]),
+    ("Nombres", &["nb"]),
+    ("Deutéronome", &["dt"]),
+    ("Josué", &["jos"]),
+    ("Juges", &["jg"]),
+    ("Ruth", &["rt"]),
+    ("1 Samuel", &["1 s"]),
+    ("2 Samuel", &["2 s"]),
+    ("1 Rois", &["1 r"]),
+    ("2 Rois", &["2 r"]),
+    ("1 Chroniques", &["1 ch"]),
+    ("2 Chroniques", &["2 ch"]),
+    ("Esdras", &["esd"]),
+    ("Néhémie", &["ne"]),
+    ("Esther", &["est"]),
+    ("Job", &["jb"]),
+    ("Psaumes", &["ps"]),
+    ("Proverbes", &["pr"]),
+    ("Ecclésiaste", &["ec", "qohélet"]),
+    ("Cantique des cantiques", &["ct", "cantique"]),
+    ("Ésaïe", &["esa"]),
+    ("Jérémie", &["jr"]),
+    ("Lamentations", &["lm"]),
+    ("Ézéchiel", &["ez"]),
+    ("Daniel", &["dn"]),
+    ("Osée", &["os"]),
+    ("Joël", &["jl"]),
+    ("Amos", &["am"]),
+    ("Abdias", &["ab"]),
+    ("Jonas", &["jon"]),
+    ("Michée", &["mi"]),
+    ("Nahum", &["na"]),
+    ("Habacuc", &["ha"]),
+    ("Sophonie", &["so"]),
+    ("Aggée", &["ag"]),
+    ("Zacharie", &["za"]),
+    ("Malachie", &["ml"]),
+    ("Matthieu", &["mt"]),
+    ("Marc", &[]),
+    ("Luc", &["lc"]),
+    ("Jean", &["jn"]),
+    ("Actes", &["ac"]),
+    ("Romains", &["rm"]),
+    ("1 Corinthiens", &["1 co"]),
+    ("2 Corinthiens", &["2 co"]),
+    ("Galates", &["ga"]),
+    ("Éphésiens", &["ep"]),
+    ("Philippiens", &["ph"]),
+    ("Colossiens", &["col"]),
+    ("1 Thessaloniciens", &["1 th"]),
+    ("2 Thessaloniciens", &["2 th"]),
+    ("1 Timothée", &["1 tm"]),
+    ("2 Timothée", &["2 tm"]),
+    ("Tite", &["tt"]),
+    ("Philémon", &["phm"]),
+    ("Hébreux", &["he"]),
+    ("Jacques", &["jc"]),
+    ("1 Pierre", &["1 p"]),
+    ("2 Pierre", &["2 p"]),
+    ("1 Jean", &["1 jn"]),
+    ("2 Jean", &["2 jn"]),
+    ("3 Jean", &["3 jn"]),
+    ("Jude", &["jud"]),
+    ("Apocalypse", &["ap"]),
+];
+
+/// Almeida names.
+const PORTUGUESE: [(&str, &[&str]); 66] = [
+    ("Gênesis", &["gn"]),
+    ("Êxodo", &["êx"]),
+    ("Levítico", &["lv"]),
+    ("Números", &["nm"]),
+    ("Deuteronômio", &["dt"]),
+    ("Josué", &["js"]),
+    ("Juízes", &["jz"]),
+    ("Rute", &["rt"]),
+    ("1 Samuel", &["1 sm"]),
+    ("2 Samuel", &["2 sm"]),
+    ("1 Reis", &["1 rs"]),
+    ("2 Reis", &["2 rs"]),
+    ("1 Crônicas", &["1 cr"]),
+    ("2 Crônicas", &["2 cr"]),
+    ("Esdras", &["ed"]),
+    ("Neemias", &["ne"]),
+    ("Ester", &["et"]),
+    ("Jó", &[]),
+    ("Salmos", &["sl"]),
+    ("Provérbios", &["pv"]),
+    ("Eclesiastes", &["ec"]),
+    ("Cânticos", &["ct", "cantares"]),
+    ("Isaías", &["is"]),
+    ("Jeremias", &["jr"]),
+    ("Lamentações", &["lm"]),
+    ("Ezequiel", &["ez"]),
+    ("Daniel", &["dn"]),
+    ("Oséias", &["os"]),
+    ("Joel", &["jl"]),
+    ("Amós", &["am"]),
+    ("Obadias", &["ob"]),
+    ("Jonas", &[]),
+    ("Miquéias", &["mq"]),
+    ("Naum", &["na"]),
+    ("Habacuque", &["hc"]),
+    ("Sofonias", &["sf"]),
+    ("Ageu", &["ag"]),
+    ("Zacarias", &["zc"]),
+    ("Malaquias", &["ml"]),
+    ("Mateus", &["mt"]),
+    ("Marcos", &["mrc"]),
+    ("Lucas", &["lc"]),
+    ("João", &[]),
+    ("Atos", &["at"]),
+    ("Romanos", &["rm"]),
+    ("1 Coríntios", &["1 co"]),
+    ("2 Coríntios", &["2 co"]),
+    ("Gálatas", &["gl"]),
+    ("Efésios", &["ef"]),
+    ("Filipenses", &["fp"]),
+    ("Colossenses", &["cl"]),
+    ("1 Tessalonicenses", &["1 ts"]),
+    ("2 Tessalonicenses", &["2 ts"]),
+    ("1 Timóteo", &["1 tm"]),
+    ("2 Timóteo", &["2 tm"]),
+    ("Tito", &["tt"]),
+    ("Filemom", &["fm"]),
+    ("Hebreus", &["hbr"]),
+    ("Tiago", &["tg"]),
+    ("1 Pedro", &["1 pe"]),
+    ("2 Pedro", &["2 pe"]),
+    ("1 João", &["1 jo"]),
+    ("2 João", &["2 jo"]),
+    ("3 João", &["3 jo"]),
+    ("Judas", &["jd"]),
+    ("Apocalipse", &["ap"]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::books::{normalize_book, BOOKS};
+
+    #[test]
+    fn every_locale_name_and_alias_reads_back_to_its_book() {
+        // Names are looked up across all locales at once, so no alias may mean
+        // different books in different languages (or in English).
+        for locale in LOCALES {
+            for (book, (name, aliases)) in BOOKS.iter().zip(locale.books) {
+                for input in std::iter::once(name).chain(aliases.iter()) {
+                    assert_eq!(
+                        normalize_book(input),
+                        Some(book.name),
+                        "{} {:?}",
+                        locale.code,
+                        input
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_locales_by_code() {
+        assert_eq!(find_locale("es").map(|l| l.code), Some("es"));
+        assert_eq!(find_locale("pt-BR").map(|l| l.code), Some("pt"));
+        assert_eq!(find_locale("GER").map(|l| l.code), Some("de"));
+        assert!(find_locale("en").is_none());
+        assert_eq!(find_locale("de").unwrap().book_name("Romans"), "Römer");
+    }
+}
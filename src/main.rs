@@ -1,15 +1,29 @@
 mod ai;
+mod align;
 mod books;
 mod cache;
 mod cli;
 mod commands;
+mod corpus;
+mod delimited;
+mod index;
+mod locales;
 mod moods;
+mod osis;
 mod output;
 mod plans;
+mod query;
+mod rank;
 mod reference;
+mod scope;
+mod stem;
+mod text;
 mod topics;
 mod tui;
+mod usfm;
+mod verify;
 mod verses;
+mod zefania;
 
 use anyhow::Result;
 use clap::{CommandFactory, Parser};
@@ -39,12 +53,20 @@ async fn main() -> Result<()> {
         .or_else(|| cache::load_default_translation(&root))
         .unwrap_or_else(|| cache::DEFAULT_TRANSLATION.to_string());
     let paths = cache::CachePaths::new(root, translation);
-    let output = output::OutputStyle::new(cli.color, cli.resolved_format());
+    // Book names display in the active translation's language, when it has one.
+    let locale = cache::read_manifest(&paths.manifest_path())
+        .and_then(|m| m.language)
+        .and_then(|language| locales::find_locale(&language));
+    let output = output::OutputStyle::new(cli.color, cli.resolved_format(), cli.ref_style)
+        .with_locale(locale);
 
     match &cli.command {
         Commands::Cache(args) => commands::run_cache(args, &paths),
         Commands::Read(args) => commands::run_read(args, &paths, &output),
         Commands::Search(args) => commands::run_search(args, &paths, &output),
+        Commands::Concordance(args) => commands::run_concordance(args, &paths, &output),
+        Commands::Similar(args) => commands::run_similar(args, &paths, &output),
+        Commands::Whereis(args) => commands::run_whereis(args, &paths, &output),
         Commands::Today(args) => commands::run_today(args, &paths, &output),
         Commands::Random(args) => commands::run_random(args, &paths, &output),
         Commands::Echo(args) => commands::run_echo(args, &paths, &output),
@@ -57,6 +79,7 @@ async fn main() -> Result<()> {
         Commands::Export(args) => commands::run_export(args, &paths, &output),
         Commands::Topic(args) => commands::run_topic(args, &paths, &output),
         Commands::Translation(args) => commands::run_translation(args, &paths),
+        Commands::Refs(args) => commands::run_refs(args, &paths, &output),
         Commands::Completions(_) => unreachable!("handled above"),
     }
 }
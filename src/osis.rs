@@ -0,0 +1,213 @@
+use anyhow::{bail, Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::books::book_from_osis;
+use crate::cache::Imported;
+use crate::verses::Verse;
+
+/// Whether a source looks like an OSIS XML document.
+pub fn is_osis(raw: &str) -> bool {
+    raw.starts_with('<') && raw.contains("<osis")
+}
+
+/// Parse an OSIS document. Verses are either containers
+/// (`<verse osisID="Gen.1.1">…</verse>`) or milestones
+/// (`<verse sID="Gen.1.1" osisID="Gen.1.1"/>…<verse eID="Gen.1.1"/>`); notes and
+/// headings inside the text are dropped. The header's first `<work>` supplies
+/// the title, and `xml:lang` (or the work's `<language>`) the language.
+pub fn parse(raw: &str) -> Result<Imported> {
+    let mut reader = Reader::from_str(raw);
+    let mut imported = Imported::default();
+    let mut current: Option<(String, u16, u16)> = None;
+    let mut text = String::new();
+    // Depth inside elements whose text is not verse text (notes, headings).
+    let mut skip = 0usize;
+    let mut in_header = false;
+    let mut works = 0usize;
+    // The header field being read: the first work's title or language.
+    let mut field: Option<&str> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .with_context(|| format!("Invalid OSIS XML at byte {}", reader.buffer_position()))?;
+        match event {
+            Event::Start(e) => {
+                let name = local_name(&e);
+                match name.as_str() {
+                    "osisText" => {
+                        if let Some(lang) = attribute(&e, "xml:lang") {
+                            imported.language = Some(lang);
+                        }
+                    }
+                    "header" => in_header = true,
+                    "work" if in_header => works += 1,
+                    "title" if in_header && works == 1 => field = Some("title"),
+                    "language" if in_header && works == 1 => field = Some("language"),
+                    "verse" => {
+                        flush(&mut imported.verses, current.take(), &mut text);
+                        current = attribute(&e, "osisID").and_then(|id| parse_osis_id(&id));
+                    }
+                    "chapter" => flush(&mut imported.verses, current.take(), &mut text),
+                    "note" | "title" if !in_header => skip += 1,
+                    _ => {}
+                }
+            }
+            Event::Empty(e) => match local_name(&e).as_str() {
+                "verse" => {
+                    flush(&mut imported.verses, current.take(), &mut text);
+                    if attribute(&e, "sID").is_some() {
+                        current = attribute(&e, "osisID").and_then(|id| parse_osis_id(&id));
+                    }
+                }
+                "chapter" => flush(&mut imported.verses, current.take(), &mut text),
+                "lb" | "l" => text.push(' '),
+                _ => {}
+            },
+            Event::End(e) => match std::str::from_utf8(e.local_name().as_ref()).unwrap_or("") {
+                "header" => in_header = false,
+                "title" | "language" if in_header => field = None,
+                "verse" | "chapter" => flush(&mut imported.verses, current.take(), &mut text),
+                "note" | "title" if !in_header => skip = skip.saturating_sub(1),
+                "l" | "p" | "lg" => text.push(' '),
+                _ => {}
+            },
+            Event::Text(e) => {
+                let value = e.unescape().context("Invalid OSIS text")?;
+                if in_header {
+                    match field {
+                        Some("title") if imported.title.is_none() => {
+                            imported.title = Some(collapse(&value));
+                        }
+                        Some("language") if imported.language.is_none() => {
+                            imported.language = Some(collapse(&value));
+                        }
+                        _ => {}
+                    }
+                } else if current.is_some() && skip == 0 {
+                    text.push_str(&value);
+                }
+            }
+            Event::CData(e) if current.is_some() && skip == 0 && !in_header => {
+                text.push_str(&String::from_utf8_lossy(&e));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    flush(&mut imported.verses, current.take(), &mut text);
+
+    if imported.verses.is_empty() {
+        bail!("No verses found in OSIS source");
+    }
+    Ok(imported)
+}
+
+pub fn local_name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.local_name().as_ref()).into_owned()
+}
+
+/// An attribute's unescaped, trimmed value, if present and not empty.
+pub fn attribute(e: &BytesStart, key: &str) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.as_ref() == key.as_bytes())
+        .and_then(|a| a.unescape_value().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// `Gen.1.1` (optionally `Bible.KJV:Gen.1.1`) as a canonical book, chapter,
+/// and verse. A verse spanning several ids (`Gen.1.1 Gen.1.2`) takes the first.
+/// Books outside the canon keep their OSIS code.
+fn parse_osis_id(id: &str) -> Option<(String, u16, u16)> {
+    let id = id.split_whitespace().next()?;
+    let id = id.rsplit(':').next()?;
+    let mut parts = id.split('.');
+    let code = parts.next()?;
+    let chapter = leading_number(parts.next()?)?;
+    let verse = leading_number(parts.next()?)?;
+    let book = book_from_osis(code).unwrap_or(code).to_string();
+    Some((book, chapter, verse))
+}
+
+/// The number at the start of an id part (`16!a` -> 16).
+fn leading_number(part: &str) -> Option<u16> {
+    let end = part
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(part.len());
+    part[..end].parse().ok()
+}
+
+fn flush(verses: &mut Vec<Verse>, current: Option<(String, u16, u16)>, text: &mut String) {
+    let body = collapse(text);
+    text.clear();
+    let Some((book, chapter, verse)) = current else {
+        return;
+    };
+    if body.is_empty() {
+        return;
+    }
+    verses.push(Verse {
+        book,
+        chapter,
+        verse,
+        text: body,
+    });
+}
+
+/// Collapse runs of whitespace (line breaks, indentation) to single spaces.
+pub fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_milestones_and_containers() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osis xmlns="http://www.bibletechnologies.net/2003/OSIS/namespace">
+  <osisText osisIDWork="KJV" xml:lang="en">
+    <header>
+      <work osisWork="KJV"><title>King James Version</title></work>
+      <work osisWork="Bible"><title>Bible</title></work>
+    </header>
+    <div type="book" osisID="Gen">
+      <chapter osisID="Gen.1">
+        <title type="chapter">Chapter 1</title>
+        <verse osisID="Gen.1.1">In the <w lemma="x">beginning</w> God created the heaven and the earth.</verse>
+        <verse osisID="Gen.1.2">And the earth was without form<note>Or, waste.</note>, and void.</verse>
+      </chapter>
+    </div>
+    <div type="book" osisID="1John">
+      <chapter sID="1John.4" osisID="1John.4"/>
+      <verse sID="1John.4.8" osisID="1John.4.8"/>He that loveth not knoweth not God;
+        <l>for God is love.</l><verse eID="1John.4.8"/>
+      <chapter eID="1John.4"/>
+    </div>
+  </osisText>
+</osis>"#;
+        assert!(is_osis(raw));
+        let imported = parse(raw).unwrap();
+        assert_eq!(imported.title.as_deref(), Some("King James Version"));
+        assert_eq!(imported.language.as_deref(), Some("en"));
+        let verses = &imported.verses;
+        assert_eq!(verses.len(), 3);
+        assert_eq!(
+            verses[0].text,
+            "In the beginning God created the heaven and the earth."
+        );
+        assert_eq!(verses[1].text, "And the earth was without form, and void.");
+        assert_eq!(
+            (verses[2].book.as_str(), verses[2].chapter, verses[2].verse),
+            ("1 John", 4, 8)
+        );
+        assert_eq!(
+            verses[2].text,
+            "He that loveth not knoweth not God; for God is love."
+        );
+    }
+}
@@ -8,8 +8,11 @@ use clap::ValueEnum;
 use serde::Serialize;
 use termimad::crossterm::style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor};
 
-use crate::books::osis_code;
+use crate::books::{is_old_testament, osis_code};
 use crate::cli::ColorMode;
+use crate::locales::Locale;
+use crate::reference::{RefStyle, ReferenceQuery};
+use crate::text::{clip_left, clip_right};
 use crate::verses::Verse;
 
 pub use markdown::MarkdownRenderer;
@@ -41,24 +44,283 @@ struct VerseRecord<'a> {
     chapter: u16,
     verse: u16,
     text: &'a str,
+    /// The input passage this verse was selected by, when several were given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    passage: Option<&'a str>,
+    /// Half-verse marker (`a`, `b`, `c`) when the reference named only part of
+    /// this verse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part: Option<char>,
+    /// Relevance score, when search results are ranked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+    /// Ranges of `text` a search matched, as byte offsets into its UTF-8 (not
+    /// character offsets, which differ once text leaves ASCII).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matches: Option<Vec<MatchSpan>>,
+    /// A verse shown around a search match (`-C`) rather than a match itself.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    context: bool,
+}
+
+/// A matched range: UTF-8 byte offsets, `end` exclusive.
+#[derive(Serialize)]
+struct MatchSpan {
+    start: usize,
+    end: usize,
+}
+
+/// A verse in search results: a match with the byte ranges of its text that
+/// matched (and its score when ranked), or a context verse shown around one.
+pub struct SearchHit<'a> {
+    pub verse: &'a Verse,
+    pub spans: Vec<(usize, usize)>,
+    pub score: Option<f64>,
+    pub context: bool,
+}
+
+/// Match counts per book and testament (`search --facets`).
+#[derive(Serialize)]
+struct FacetsRecord<'a> {
+    total: usize,
+    testaments: TestamentCounts,
+    books: Vec<BookCount<'a>>,
+}
+
+#[derive(Serialize, Default, Debug, PartialEq)]
+struct TestamentCounts {
+    ot: usize,
+    nt: usize,
+    /// Books outside the 66-book canon (e.g. the Apocrypha).
+    #[serde(skip_serializing_if = "is_zero")]
+    other: usize,
+}
+
+impl TestamentCounts {
+    /// Sum per-book counts by testament.
+    fn tally(counts: &[(&str, usize)]) -> Self {
+        let mut testaments = TestamentCounts::default();
+        for (book, count) in counts {
+            match is_old_testament(book) {
+                Some(true) => testaments.ot += count,
+                Some(false) => testaments.nt += count,
+                None => testaments.other += count,
+            }
+        }
+        testaments
+    }
+
+    fn total(&self) -> usize {
+        self.ot + self.nt + self.other
+    }
+}
+
+/// The closing line of NDJSON `search --facets`, after one line per book.
+#[derive(Serialize)]
+struct FacetTotals {
+    total: usize,
+    testaments: TestamentCounts,
+}
+
+#[derive(Serialize)]
+struct BookCount<'a> {
+    book: &'a str,
+    count: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// Width of the longest bar in `search --facets`.
+const FACET_BAR_WIDTH: usize = 30;
+
+/// A likely source of a quotation (`whereis`): one verse or a run of
+/// consecutive verses, with the quotation's words marked and a confidence.
+pub struct QuoteSource<'a> {
+    pub hits: Vec<SearchHit<'a>>,
+    pub confidence: f64,
+}
+
+#[derive(Serialize)]
+struct QuoteSourceRecord<'a> {
+    passage: String,
+    confidence: f64,
+    verses: Vec<VerseRecord<'a>>,
+}
+
+impl<'a> QuoteSourceRecord<'a> {
+    fn new(source: &'a QuoteSource<'a>, output: &OutputStyle) -> Self {
+        Self {
+            passage: output.source_passage(source),
+            confidence: (source.confidence * 1000.0).round() / 1000.0,
+            verses: source
+                .hits
+                .iter()
+                .map(|hit| VerseRecord::new(hit.verse, output).with_hit(hit))
+                .collect(),
+        }
+    }
+}
+
+/// One occurrence of a word in a verse, as byte offsets into its text.
+pub struct Occurrence<'a> {
+    pub verse: &'a Verse,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl<'a> Occurrence<'a> {
+    /// Left context, keyword, and right context, each side clipped to `width`.
+    fn columns(&self, width: usize) -> (String, &'a str, String) {
+        let text = &self.verse.text;
+        (
+            clip_left(&text[..self.start], width),
+            &text[self.start..self.end],
+            clip_right(&text[self.end..], width),
+        )
+    }
+
+    /// The unstyled concordance line: the left context right-aligned to
+    /// `width` columns, so every keyword starts in the same column.
+    fn line(&self, width: usize) -> String {
+        let (left, keyword, right) = self.columns(width);
+        format!("{:>width$}{}{}", left, keyword, right, width = width)
+    }
+}
+
+/// A concordance line: the keyword with its verse text either side.
+#[derive(Serialize)]
+struct OccurrenceRecord<'a> {
+    id: String,
+    reference: String,
+    book: &'a str,
+    chapter: u16,
+    verse: u16,
+    left: &'a str,
+    keyword: &'a str,
+    right: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> OccurrenceRecord<'a> {
+    fn new(o: &Occurrence<'a>, output: &OutputStyle) -> Self {
+        let v = o.verse;
+        Self {
+            id: format!("{}.{}.{}", osis_code(&v.book), v.chapter, v.verse),
+            reference: output.reference(v),
+            book: &v.book,
+            chapter: v.chapter,
+            verse: v.verse,
+            left: &v.text[..o.start],
+            keyword: &v.text[o.start..o.end],
+            right: &v.text[o.end..],
+            start: o.start,
+            end: o.end,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ConcordanceBook<'a> {
+    book: &'a str,
+    count: usize,
+    occurrences: Vec<OccurrenceRecord<'a>>,
+}
+
+/// One verse's matches across translations (`search --in`).
+#[derive(Serialize)]
+struct TranslationsRecord<'a> {
+    id: String,
+    reference: String,
+    book: &'a str,
+    chapter: u16,
+    verse: u16,
+    translations: Vec<TranslationMatch<'a>>,
+}
+
+#[derive(Serialize)]
+struct TranslationMatch<'a> {
+    translation: &'a str,
+    text: &'a str,
+    /// Byte offsets into `text`, as in `VerseRecord::matches`.
+    matches: Vec<MatchSpan>,
+}
+
+impl<'a> TranslationsRecord<'a> {
+    fn new(group: &'a [(&'a str, SearchHit<'a>)], output: &OutputStyle) -> Self {
+        let v = group[0].1.verse;
+        Self {
+            id: format!("{}.{}.{}", osis_code(&v.book), v.chapter, v.verse),
+            reference: output.reference(v),
+            book: &v.book,
+            chapter: v.chapter,
+            verse: v.verse,
+            translations: group
+                .iter()
+                .map(|(id, hit)| TranslationMatch {
+                    translation: id,
+                    text: &hit.verse.text,
+                    matches: hit
+                        .spans
+                        .iter()
+                        .map(|&(start, end)| MatchSpan { start, end })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
 }
 
 /// Serialize verses to a pretty JSON array of records (id, reference, fields).
 /// Independent of the active output format — used by `export --to json`.
-pub fn verses_to_json(verses: &[&Verse]) -> String {
-    let records: Vec<VerseRecord> = verses.iter().map(|v| VerseRecord::new(v)).collect();
+pub fn verses_to_json(verses: &[&Verse], output: &OutputStyle) -> String {
+    let records: Vec<VerseRecord> = verses.iter().map(|v| VerseRecord::new(v, output)).collect();
     serde_json::to_string_pretty(&records).unwrap_or_else(|_| "[]".to_string())
 }
 
 impl<'a> VerseRecord<'a> {
-    fn new(v: &'a Verse) -> Self {
+    fn new(v: &'a Verse, output: &OutputStyle) -> Self {
         Self {
             id: format!("{}.{}.{}", osis_code(&v.book), v.chapter, v.verse),
-            reference: format!("{} {}:{}", v.book, v.chapter, v.verse),
+            reference: output.reference(v),
             book: &v.book,
             chapter: v.chapter,
             verse: v.verse,
             text: &v.text,
+            passage: None,
+            part: None,
+            score: None,
+            matches: None,
+            context: false,
+        }
+    }
+
+    fn with_part(self, part: Option<char>) -> Self {
+        Self { part, ..self }
+    }
+
+    /// A search hit's matches, context flag, and score (rounded for display).
+    fn with_hit(self, hit: &SearchHit) -> Self {
+        let matches = (!hit.context).then(|| {
+            hit.spans
+                .iter()
+                .map(|&(start, end)| MatchSpan { start, end })
+                .collect()
+        });
+        Self {
+            score: hit.score.map(|score| (score * 1000.0).round() / 1000.0),
+            matches,
+            context: hit.context,
+            ..self
+        }
+    }
+
+    fn in_passage(self, label: &'a str) -> Self {
+        Self {
+            passage: Some(label),
+            ..self
         }
     }
 }
@@ -67,6 +329,11 @@ pub struct OutputStyle {
     pub color: bool,
     pub theme: Theme,
     pub format: Format,
+    /// How references are written in verse lines and records.
+    pub ref_style: RefStyle,
+    /// Language of book names in full-style references (the active
+    /// translation's), or `None` for English.
+    pub locale: Option<&'static Locale>,
 }
 
 pub struct Theme {
@@ -90,7 +357,7 @@ impl Theme {
 }
 
 impl OutputStyle {
-    pub fn new(mode: ColorMode, format: Format) -> Self {
+    pub fn new(mode: ColorMode, format: Format, ref_style: RefStyle) -> Self {
         let mut color = match mode {
             ColorMode::Always => true,
             ColorMode::Never => false,
@@ -104,9 +371,16 @@ impl OutputStyle {
             color,
             theme: Theme::claude_code(),
             format,
+            ref_style,
+            locale: None,
         }
     }
 
+    /// Write full book names in `locale`'s language.
+    pub fn with_locale(self, locale: Option<&'static Locale>) -> Self {
+        Self { locale, ..self }
+    }
+
     /// True when output is a machine-readable format rather than the decorated
     /// human view. Commands use this to suppress prompts, headers, and banners.
     pub fn is_structured(&self) -> bool {
@@ -115,10 +389,22 @@ impl OutputStyle {
 
     /// Render a set of verses according to the active format.
     pub fn emit_verses(&self, verses: &[&Verse]) {
+        self.emit_selection(verses, None);
+    }
+
+    /// Render the verses a reference resolved to. Half-verse markers in the
+    /// reference (`Romans 3:23a`) are shown on the references of the verses they
+    /// apply to and reported as `part` in JSON and NDJSON records.
+    pub fn emit_reference(&self, reference: &ReferenceQuery, verses: &[&Verse]) {
+        self.emit_selection(verses, Some(reference));
+    }
+
+    fn emit_selection(&self, verses: &[&Verse], reference: Option<&ReferenceQuery>) {
+        let part = |i: usize| reference.and_then(|r| r.part_at(i, verses.len()));
         match self.format {
             Format::Plain => {
-                for v in verses {
-                    println!("{}", self.verse_line(v));
+                for (i, v) in verses.iter().enumerate() {
+                    println!("{}", self.verse_line_with_part(v, part(i)));
                 }
             }
             Format::Raw => {
@@ -127,8 +413,8 @@ impl OutputStyle {
                 }
             }
             Format::Ref => {
-                for v in verses {
-                    println!("{} {}:{}", v.book, v.chapter, v.verse);
+                for (i, v) in verses.iter().enumerate() {
+                    println!("{}", self.reference_with_part(v, part(i)));
                 }
             }
             Format::Tsv => {
@@ -146,49 +432,570 @@ impl OutputStyle {
                 }
             }
             Format::Ndjson => {
-                for v in verses {
-                    if let Ok(line) = serde_json::to_string(&VerseRecord::new(v)) {
+                for (i, v) in verses.iter().enumerate() {
+                    let record = VerseRecord::new(v, self).with_part(part(i));
+                    if let Ok(line) = serde_json::to_string(&record) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Format::Json => {
+                let records: Vec<VerseRecord> = verses
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| VerseRecord::new(v, self).with_part(part(i)))
+                    .collect();
+                match serde_json::to_string_pretty(&records) {
+                    Ok(json) => println!("{}", json),
+                    Err(_) => println!("[]"),
+                }
+            }
+        }
+    }
+
+    /// Render search results. JSON and NDJSON records carry each match's byte
+    /// ranges as `matches` (and `score` when ranked); the plain view highlights
+    /// them. With `context`, each group is a match and its surrounding verses:
+    /// the plain view marks matches with `*` and separates groups with `--`.
+    pub fn emit_hits(&self, groups: &[Vec<SearchHit>], context: bool) {
+        let hits = || groups.iter().flatten();
+        let records = || hits().map(|hit| VerseRecord::new(hit.verse, self).with_hit(hit));
+        match self.format {
+            Format::Json => {
+                let records: Vec<VerseRecord> = records().collect();
+                match serde_json::to_string_pretty(&records) {
+                    Ok(json) => println!("{}", json),
+                    Err(_) => println!("[]"),
+                }
+            }
+            Format::Ndjson => {
+                for record in records() {
+                    if let Ok(line) = serde_json::to_string(&record) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Format::Plain => {
+                for (n, group) in groups.iter().enumerate() {
+                    if context && n > 0 {
+                        self.print_dim("--");
+                    }
+                    for hit in group {
+                        let line = self.line(
+                            &self.reference(hit.verse),
+                            &self.highlight(&hit.verse.text, &hit.spans),
+                        );
+                        if context {
+                            println!("{}", self.marked(if hit.context { " " } else { "*" }, line));
+                        } else {
+                            println!("{}", line);
+                        }
+                    }
+                }
+            }
+            Format::Tsv | Format::Ref | Format::Raw => {
+                let verses: Vec<&Verse> = hits().map(|hit| hit.verse).collect();
+                self.emit_verses(&verses);
+            }
+        }
+    }
+
+    /// Render search matches grouped by verse, each group holding the
+    /// translations that matched it (by id) and their text. The plain view is
+    /// laid out like `parallel`, with matches highlighted; TSV adds the
+    /// translation id after the verse id.
+    pub fn emit_translation_hits(&self, groups: &[Vec<(&str, SearchHit)>]) {
+        match self.format {
+            Format::Json => {
+                let records: Vec<TranslationsRecord> = groups
+                    .iter()
+                    .map(|g| TranslationsRecord::new(g, self))
+                    .collect();
+                match serde_json::to_string_pretty(&records) {
+                    Ok(json) => println!("{}", json),
+                    Err(_) => println!("[]"),
+                }
+            }
+            Format::Ndjson => {
+                for group in groups {
+                    if let Ok(line) = serde_json::to_string(&TranslationsRecord::new(group, self)) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Format::Plain => {
+                let label_width = groups
+                    .iter()
+                    .flatten()
+                    .map(|(id, _)| id.len())
+                    .max()
+                    .unwrap_or(3);
+                for (n, group) in groups.iter().enumerate() {
+                    if n > 0 {
+                        println!();
+                    }
+                    self.print_reference_heading(&self.reference(group[0].1.verse));
+                    for (id, hit) in group {
+                        println!(
+                            "  {:width$}  {}",
+                            id,
+                            self.highlight(&hit.verse.text, &hit.spans),
+                            width = label_width
+                        );
+                    }
+                }
+            }
+            Format::Tsv => {
+                for (id, hit) in groups.iter().flatten() {
+                    let v = hit.verse;
+                    println!(
+                        "{}.{}.{}\t{}\t{}\t{}\t{}\t{}",
+                        osis_code(&v.book),
+                        v.chapter,
+                        v.verse,
+                        id,
+                        v.book,
+                        v.chapter,
+                        v.verse,
+                        v.text
+                    );
+                }
+            }
+            Format::Ref => {
+                for group in groups {
+                    println!("{}", self.reference(group[0].1.verse));
+                }
+            }
+            Format::Raw => {
+                for (_, hit) in groups.iter().flatten() {
+                    println!("{}", hit.verse.text);
+                }
+            }
+        }
+    }
+
+    /// Render per-book match counts (in the order given) with testament
+    /// totals. The plain view draws a bar per book scaled to the largest count;
+    /// JSON gives the same breakdown; NDJSON and TSV give one book per line,
+    /// then the testament totals and the overall total.
+    pub fn emit_facets(&self, counts: &[(&str, usize)]) {
+        let testaments = TestamentCounts::tally(counts);
+        let total = testaments.total();
+        let books = || {
+            counts
+                .iter()
+                .map(|&(book, count)| BookCount { book, count })
+        };
+        match self.format {
+            Format::Json => {
+                let record = FacetsRecord {
+                    total,
+                    testaments,
+                    books: books().collect(),
+                };
+                match serde_json::to_string_pretty(&record) {
+                    Ok(json) => println!("{}", json),
+                    Err(_) => println!("{{}}"),
+                }
+            }
+            Format::Ndjson => {
+                for record in books() {
+                    if let Ok(line) = serde_json::to_string(&record) {
                         println!("{}", line);
                     }
                 }
+                if let Ok(line) = serde_json::to_string(&FacetTotals { total, testaments }) {
+                    println!("{}", line);
+                }
+            }
+            Format::Tsv | Format::Raw => {
+                for (book, count) in counts {
+                    println!("{}\t{}", book, count);
+                }
+                println!("Old Testament\t{}", testaments.ot);
+                println!("New Testament\t{}", testaments.nt);
+                if testaments.other > 0 {
+                    println!("Other\t{}", testaments.other);
+                }
+                println!("Total\t{}", total);
             }
+            Format::Ref => {
+                for (book, _) in counts {
+                    println!("{}", book);
+                }
+            }
+            Format::Plain => {
+                let names: Vec<String> = counts
+                    .iter()
+                    .map(|(book, _)| self.ref_style.book(book, self.locale))
+                    .collect();
+                let name_width = names
+                    .iter()
+                    .map(|name| name.chars().count())
+                    .chain(["Old Testament".len()])
+                    .max()
+                    .unwrap_or(0);
+                let max = counts.iter().map(|(_, count)| *count).max().unwrap_or(1);
+                let count_width = max.to_string().len().max(total.to_string().len());
+                for (name, (_, count)) in names.iter().zip(counts) {
+                    let bar = "█".repeat((count * FACET_BAR_WIDTH).div_ceil(max));
+                    println!(
+                        "{:name_width$}  {:>count_width$}  {}",
+                        name,
+                        count,
+                        self.span(&bar, self.theme.marker)
+                    );
+                }
+                println!();
+                let books_in = |old: Option<bool>| {
+                    counts
+                        .iter()
+                        .filter(|(book, _)| is_old_testament(book) == old)
+                        .count()
+                };
+                for (label, total, old) in [
+                    ("Old Testament", testaments.ot, Some(true)),
+                    ("New Testament", testaments.nt, Some(false)),
+                    ("Other", testaments.other, None),
+                ] {
+                    if total == 0 && old.is_none() {
+                        continue;
+                    }
+                    let books = books_in(old);
+                    println!(
+                        "{:name_width$}  {:>count_width$}  {}",
+                        label,
+                        total,
+                        self.span(
+                            &format!("in {} book{}", books, if books == 1 { "" } else { "s" }),
+                            self.theme.dim
+                        )
+                    );
+                }
+                println!(
+                    "{:name_width$}  {:>count_width$}  {}",
+                    "Total",
+                    total,
+                    self.span(
+                        &format!(
+                            "in {} book{}",
+                            counts.len(),
+                            if counts.len() == 1 { "" } else { "s" }
+                        ),
+                        self.theme.dim
+                    )
+                );
+            }
+        }
+    }
+
+    /// Render likely sources of a quotation, best first. The plain view heads
+    /// each with its passage and confidence; JSON and NDJSON records carry the
+    /// passage, the confidence (0 to 1), and its verses with matched words.
+    pub fn emit_quote_sources(&self, sources: &[QuoteSource]) {
+        let text = |source: &QuoteSource| {
+            let texts: Vec<&str> = source.hits.iter().map(|h| h.verse.text.as_str()).collect();
+            texts.join(" ")
+        };
+        match self.format {
             Format::Json => {
-                let records: Vec<VerseRecord> =
-                    verses.iter().map(|v| VerseRecord::new(v)).collect();
+                let records: Vec<QuoteSourceRecord> = sources
+                    .iter()
+                    .map(|s| QuoteSourceRecord::new(s, self))
+                    .collect();
                 match serde_json::to_string_pretty(&records) {
                     Ok(json) => println!("{}", json),
                     Err(_) => println!("[]"),
                 }
             }
+            Format::Ndjson => {
+                for source in sources {
+                    if let Ok(line) = serde_json::to_string(&QuoteSourceRecord::new(source, self)) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Format::Plain => {
+                for (n, source) in sources.iter().enumerate() {
+                    if n > 0 {
+                        println!();
+                    }
+                    self.print_reference_heading(&format!(
+                        "{}  {:.0}%",
+                        self.source_passage(source),
+                        source.confidence * 100.0
+                    ));
+                    for hit in &source.hits {
+                        println!(
+                            "{}",
+                            self.line(
+                                &self.reference(hit.verse),
+                                &self.highlight(&hit.verse.text, &hit.spans)
+                            )
+                        );
+                    }
+                }
+            }
+            Format::Tsv => {
+                for source in sources {
+                    println!(
+                        "{}\t{:.3}\t{}",
+                        self.source_passage(source),
+                        source.confidence,
+                        text(source)
+                    );
+                }
+            }
+            Format::Ref => {
+                for source in sources {
+                    println!("{}", self.source_passage(source));
+                }
+            }
+            Format::Raw => {
+                for source in sources {
+                    println!("{}", text(source));
+                }
+            }
         }
     }
 
+    /// A quotation source's passage: its verse, or its first verse through the
+    /// last one's number (`John 3:16-17`).
+    fn source_passage(&self, source: &QuoteSource) -> String {
+        let first = self.reference(source.hits[0].verse);
+        match source.hits.as_slice() {
+            [_, .., last] => format!("{}-{}", first, last.verse.verse),
+            _ => first,
+        }
+    }
+
+    /// Render a concordance: every occurrence of a keyword, grouped by book
+    /// with per-book counts. The plain view aligns the keyword in one column
+    /// between `width` characters of context; JSON nests occurrences under
+    /// their book, and NDJSON, TSV, and `ref` give one occurrence per line.
+    pub fn emit_concordance(&self, books: &[(&str, Vec<Occurrence>)], width: usize) {
+        let occurrences = || books.iter().flat_map(|(_, list)| list);
+        match self.format {
+            Format::Json => {
+                let records: Vec<ConcordanceBook> = books
+                    .iter()
+                    .map(|(book, list)| ConcordanceBook {
+                        book,
+                        count: list.len(),
+                        occurrences: list
+                            .iter()
+                            .map(|o| OccurrenceRecord::new(o, self))
+                            .collect(),
+                    })
+                    .collect();
+                match serde_json::to_string_pretty(&records) {
+                    Ok(json) => println!("{}", json),
+                    Err(_) => println!("[]"),
+                }
+            }
+            Format::Ndjson => {
+                for o in occurrences() {
+                    if let Ok(line) = serde_json::to_string(&OccurrenceRecord::new(o, self)) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Format::Tsv => {
+                for o in occurrences() {
+                    let record = OccurrenceRecord::new(o, self);
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                        record.id,
+                        record.book,
+                        record.chapter,
+                        record.verse,
+                        record.left,
+                        record.keyword,
+                        record.right
+                    );
+                }
+            }
+            Format::Ref => {
+                for o in occurrences() {
+                    println!("{}", self.reference(o.verse));
+                }
+            }
+            Format::Raw => {
+                for o in occurrences() {
+                    println!("{}", o.line(width));
+                }
+            }
+            Format::Plain => {
+                let ref_width = occurrences()
+                    .map(|o| self.reference(o.verse).chars().count())
+                    .max()
+                    .unwrap_or(0);
+                for (n, (book, list)) in books.iter().enumerate() {
+                    if n > 0 {
+                        println!();
+                    }
+                    let name = self.ref_style.book(book, self.locale);
+                    self.print_reference_heading(&format!("{} ({})", name, list.len()));
+                    for o in list {
+                        let (left, keyword, right) = o.columns(width);
+                        println!(
+                            "  {:ref_width$}  {:>width$}{}{}",
+                            self.reference(o.verse),
+                            left,
+                            self.span(keyword, self.theme.marker),
+                            right,
+                            width = width
+                        );
+                    }
+                }
+                let total: usize = books.iter().map(|(_, list)| list.len()).sum();
+                println!();
+                self.print_dim(&format!(
+                    "{} occurrence{} in {} book{}",
+                    total,
+                    if total == 1 { "" } else { "s" },
+                    books.len(),
+                    if books.len() == 1 { "" } else { "s" }
+                ));
+            }
+        }
+    }
+
+    /// Highlight byte ranges of `text` (search matches) when color is enabled.
+    /// Ranges must be ascending and not overlap.
+    pub fn highlight(&self, text: &str, spans: &[(usize, usize)]) -> String {
+        if !self.color {
+            return text.to_string();
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut at = 0;
+        for &(start, end) in spans {
+            out.push_str(&text[at..start]);
+            out.push_str(&self.span(&text[start..end], self.theme.marker));
+            at = end;
+        }
+        out.push_str(&text[at..]);
+        out
+    }
+
+    /// Render several passages at once, each labeled by its normalized reference.
+    /// JSON and NDJSON records carry a `passage` field naming their passage; the
+    /// plain view separates passages with a blank line.
+    pub fn emit_passages(&self, passages: &[(&ReferenceQuery, Vec<&Verse>)]) {
+        let labels: Vec<String> = passages.iter().map(|(r, _)| self.passage(r)).collect();
+        let records = || {
+            passages
+                .iter()
+                .zip(&labels)
+                .flat_map(|((reference, verses), label)| {
+                    verses.iter().enumerate().map(move |(i, v)| {
+                        VerseRecord::new(v, self)
+                            .in_passage(label)
+                            .with_part(reference.part_at(i, verses.len()))
+                    })
+                })
+        };
+        match self.format {
+            Format::Json => {
+                let records: Vec<VerseRecord> = records().collect();
+                match serde_json::to_string_pretty(&records) {
+                    Ok(json) => println!("{}", json),
+                    Err(_) => println!("[]"),
+                }
+            }
+            Format::Ndjson => {
+                for record in records() {
+                    if let Ok(line) = serde_json::to_string(&record) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Format::Plain => {
+                for (n, (reference, verses)) in passages.iter().enumerate() {
+                    if n > 0 {
+                        println!();
+                    }
+                    self.emit_reference(reference, verses);
+                }
+            }
+            Format::Tsv | Format::Ref | Format::Raw => {
+                for (reference, verses) in passages {
+                    self.emit_reference(reference, verses);
+                }
+            }
+        }
+    }
+
+    /// A verse's reference in the active reference style.
+    pub fn reference(&self, verse: &Verse) -> String {
+        self.verse_reference(&verse.book, verse.chapter, verse.verse)
+    }
+
+    /// A single verse reference in the active reference style.
+    pub fn verse_reference(&self, book: &str, chapter: u16, verse: u16) -> String {
+        self.ref_style.verse(book, chapter, verse, self.locale)
+    }
+
+    /// A parsed reference in the active reference style.
+    pub fn passage(&self, reference: &ReferenceQuery) -> String {
+        reference.styled_in(self.ref_style, self.locale)
+    }
+
+    /// A canonical book name in the active reference style.
+    pub fn book_name(&self, book: &str) -> String {
+        self.ref_style.book(book, self.locale)
+    }
+
+    /// A verse's reference with a half-verse marker (`Romans 3:23a`, or the
+    /// OSIS grain `Rom.3.23!a`).
+    fn reference_with_part(&self, verse: &Verse, part: Option<char>) -> String {
+        let mut reference = self.reference(verse);
+        if let Some(part) = part {
+            if self.ref_style == RefStyle::Osis {
+                reference.push('!');
+            }
+            reference.push(part);
+        }
+        reference
+    }
+
     pub fn verse_line(&self, verse: &Verse) -> String {
-        let reference = format!("{} {}:{}", verse.book, verse.chapter, verse.verse);
+        self.verse_line_with_part(verse, None)
+    }
+
+    fn verse_line_with_part(&self, verse: &Verse, part: Option<char>) -> String {
+        self.line(&self.reference_with_part(verse, part), &verse.text)
+    }
+
+    fn line(&self, reference: &str, text: &str) -> String {
         if self.color {
             format!(
                 "{}{}{}  {}",
                 SetForegroundColor(self.theme.reference),
                 reference,
                 ResetColor,
-                verse.text
+                text
             )
         } else {
-            format!("{}  {}", reference, verse.text)
+            format!("{}  {}", reference, text)
         }
     }
 
     pub fn marked_verse_line(&self, marker: &str, verse: &Verse) -> String {
+        self.marked(marker, self.verse_line(verse))
+    }
+
+    fn marked(&self, marker: &str, line: String) -> String {
         if self.color && marker == "*" {
             format!(
                 "{}{}{} {}",
                 SetForegroundColor(self.theme.marker),
                 marker,
                 ResetColor,
-                self.verse_line(verse)
+                line
             )
         } else {
-            format!("{} {}", marker, self.verse_line(verse))
+            format!("{} {}", marker, line)
         }
     }
 
@@ -299,3 +1106,54 @@ fn terminal_width() -> usize {
         .map(|(w, _)| w as usize)
         .unwrap_or(80)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn facets_split_counts_by_testament() {
+        let counts = [("Genesis", 3), ("Malachi", 1), ("John", 4), ("Tobit", 2)];
+        let testaments = TestamentCounts::tally(&counts);
+        assert_eq!(
+            testaments,
+            TestamentCounts {
+                ot: 4,
+                nt: 4,
+                other: 2
+            }
+        );
+        assert_eq!(testaments.total(), 10);
+    }
+
+    #[test]
+    fn concordance_lines_align_the_keyword() {
+        let verses = [
+            Verse::new(
+                "Genesis",
+                1,
+                3,
+                "And God said, Let there be light: and there was light.",
+            ),
+            Verse::new("John", 8, 12, "I am the light of the world"),
+        ];
+        let occurrence = |verse: usize, word: &str| {
+            let start = verses[verse].text.find(word).unwrap();
+            Occurrence {
+                verse: &verses[verse],
+                start,
+                end: start + word.len(),
+            }
+        };
+        let lines = [
+            occurrence(0, "light").line(12),
+            occurrence(1, "light").line(12),
+        ];
+        assert_eq!(lines[0], "…t there be light: and there…");
+        assert_eq!(lines[1], "   I am the light of the wor…");
+        for line in &lines {
+            let column = line.chars().take_while(|c| *c != 'l').count();
+            assert_eq!(column, 12);
+        }
+    }
+}
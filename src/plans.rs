@@ -212,12 +212,7 @@ mod tests {
         let mut verses = Vec::new();
         for (book, chapters) in books {
             for chapter in 1..=*chapters {
-                verses.push(Verse {
-                    book: book.to_string(),
-                    chapter,
-                    verse: 1,
-                    text: "In the beginning".to_string(),
-                });
+                verses.push(Verse::new(book, chapter, 1, "In the beginning"));
             }
         }
         verses
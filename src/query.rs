@@ -0,0 +1,382 @@
+use anyhow::{bail, Result};
+
+use crate::index::{intersect, words};
+use crate::stem::stem;
+
+/// Where a word or phrase can occur: verse positions, or `None` for anywhere.
+pub type Lookup<'a> = dyn Fn(&[String]) -> Option<Vec<usize>> + 'a;
+
+/// A boolean search query, parsed from e.g.
+/// `faith AND works NOT dead`, `"born again" OR (grace NEAR/5 faith)`.
+///
+/// Words match whole words, case-insensitively. Operators are uppercase
+/// (`AND`, `OR`, `NOT`, `NEAR/n`), so lowercase `and`/`or`/`not` are ordinary
+/// words. Adjacent terms are joined by an implicit AND; NOT binds tightest, then
+/// NEAR, AND, and OR.
+#[derive(Debug, PartialEq)]
+pub enum Query {
+    /// A word, or a quoted phrase of consecutive words (lowercased).
+    Words(Vec<String>),
+    /// Two words or phrases at most `n` words apart, in either order.
+    Near(Vec<String>, Vec<String>, usize),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+/// `NEAR` without a distance.
+const DEFAULT_NEAR: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Words(Vec<String>),
+    And,
+    Or,
+    Not,
+    Near(usize),
+    Open,
+    Close,
+}
+
+pub fn parse_query(input: &str) -> Result<Query> {
+    let tokens = lex(input)?;
+    if tokens.is_empty() {
+        bail!("Invalid query: it is empty");
+    }
+    let mut parser = Parser { tokens, at: 0 };
+    let query = parser.or()?;
+    if let Some(token) = parser.peek() {
+        bail!("Invalid query: unexpected {}", describe(token));
+    }
+    Ok(query)
+}
+
+fn lex(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            continue;
+        }
+        if c == '"' {
+            chars.next();
+            let mut phrase = String::new();
+            let mut closed = false;
+            for (_, c) in chars.by_ref() {
+                if c == '"' {
+                    closed = true;
+                    break;
+                }
+                phrase.push(c);
+            }
+            if !closed {
+                bail!("Invalid query: unterminated quote");
+            }
+            let phrase = words(&phrase);
+            if phrase.is_empty() {
+                bail!("Invalid query: empty phrase");
+            }
+            tokens.push(Token::Words(phrase));
+            continue;
+        }
+        let mut end = input.len();
+        while let Some(&(at, c)) = chars.peek() {
+            if c.is_whitespace() || matches!(c, '(' | ')' | '"') {
+                end = at;
+                break;
+            }
+            chars.next();
+        }
+        let raw = &input[start..end];
+        tokens.push(match raw {
+            "AND" => Token::And,
+            "OR" => Token::Or,
+            "NOT" => Token::Not,
+            "NEAR" => Token::Near(DEFAULT_NEAR),
+            _ => match raw.strip_prefix("NEAR/") {
+                Some(n) => Token::Near(
+                    n.parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid query: bad distance in {}", raw))?,
+                ),
+                None => {
+                    let term = words(raw);
+                    if term.is_empty() {
+                        bail!("Invalid query: '{}' has no words to match", raw);
+                    }
+                    Token::Words(term)
+                }
+            },
+        });
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Words(words) => format!("'{}'", words.join(" ")),
+        Token::And => "AND".to_string(),
+        Token::Or => "OR".to_string(),
+        Token::Not => "NOT".to_string(),
+        Token::Near(n) => format!("NEAR/{}", n),
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    at: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.at)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.at).cloned();
+        self.at += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Query> {
+        let mut terms = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            terms.push(self.and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Query::Or(terms)
+        })
+    }
+
+    fn and(&mut self) -> Result<Query> {
+        let mut terms = vec![self.unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                }
+                Some(Token::Words(_) | Token::Not | Token::Open) => {}
+                _ => break,
+            }
+            terms.push(self.unary()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Query::And(terms)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Query> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Query::Not(Box::new(self.unary()?)));
+        }
+        let left = self.primary()?;
+        let Some(&Token::Near(distance)) = self.peek() else {
+            return Ok(left);
+        };
+        self.next();
+        let right = self.primary()?;
+        match (left, right) {
+            (Query::Words(left), Query::Words(right)) => {
+                if let Some(Token::Near(_)) = self.peek() {
+                    bail!("Invalid query: NEAR cannot be chained; join the pairs with AND");
+                }
+                Ok(Query::Near(left, right, distance))
+            }
+            _ => bail!("Invalid query: NEAR joins two words or quoted phrases"),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Query> {
+        match self.next() {
+            Some(Token::Words(words)) => Ok(Query::Words(words)),
+            Some(Token::Open) => {
+                let inner = self.or()?;
+                if self.next() != Some(Token::Close) {
+                    bail!("Invalid query: missing ')'");
+                }
+                Ok(inner)
+            }
+            Some(token) => bail!("Invalid query: unexpected {}", describe(&token)),
+            None => bail!("Invalid query: it ends after an operator"),
+        }
+    }
+}
+
+impl Query {
+    /// Whether a verse matches, given its lowercased words in order.
+    pub fn matches(&self, text: &[String]) -> bool {
+        match self {
+            Query::Words(phrase) => !spans(text, phrase).is_empty(),
+            Query::Near(left, right, distance) => {
+                let right = spans(text, right);
+                spans(text, left)
+                    .iter()
+                    .any(|a| right.iter().any(|b| gap(*a, *b) <= *distance))
+            }
+            Query::Not(inner) => !inner.matches(text),
+            Query::And(terms) => terms.iter().all(|t| t.matches(text)),
+            Query::Or(terms) => terms.iter().any(|t| t.matches(text)),
+        }
+    }
+
+    /// The words a match is scored on: every word the query asks for, but not
+    /// the ones it excludes with NOT.
+    pub fn terms(&self) -> Vec<&str> {
+        match self {
+            Query::Words(phrase) => phrase.iter().map(String::as_str).collect(),
+            Query::Near(left, right, _) => left.iter().chain(right).map(String::as_str).collect(),
+            Query::Not(_) => Vec::new(),
+            Query::And(terms) | Query::Or(terms) => terms.iter().flat_map(Query::terms).collect(),
+        }
+    }
+
+    /// The same query over word stems (`--stem`), to be matched against the
+    /// stems of a verse's words.
+    pub fn stemmed(self) -> Query {
+        let stems = |phrase: Vec<String>| phrase.iter().map(|w| stem(w)).collect();
+        match self {
+            Query::Words(phrase) => Query::Words(stems(phrase)),
+            Query::Near(left, right, distance) => Query::Near(stems(left), stems(right), distance),
+            Query::Not(inner) => Query::Not(Box::new(inner.stemmed())),
+            Query::And(terms) => Query::And(terms.into_iter().map(Query::stemmed).collect()),
+            Query::Or(terms) => Query::Or(terms.into_iter().map(Query::stemmed).collect()),
+        }
+    }
+
+    /// Verse positions that can match, given a `lookup` of the positions that
+    /// can hold a word or phrase (ascending, as from the search index); `None`
+    /// when the query can't be narrowed (e.g. a bare `NOT`).
+    pub fn candidates(&self, lookup: &Lookup) -> Option<Vec<usize>> {
+        match self {
+            Query::Words(phrase) => lookup(phrase),
+            Query::Near(left, right, _) => Some(intersect(&lookup(left)?, &lookup(right)?)),
+            Query::Not(_) => None,
+            Query::And(terms) => terms
+                .iter()
+                .filter_map(|t| t.candidates(lookup))
+                .reduce(|a, b| intersect(&a, &b)),
+            Query::Or(terms) => {
+                let mut out = Vec::new();
+                for term in terms {
+                    out.extend(term.candidates(lookup)?);
+                }
+                out.sort_unstable();
+                out.dedup();
+                Some(out)
+            }
+        }
+    }
+}
+
+/// Inclusive word spans where `phrase` occurs in `text`.
+fn spans(text: &[String], phrase: &[String]) -> Vec<(usize, usize)> {
+    if phrase.is_empty() || phrase.len() > text.len() {
+        return Vec::new();
+    }
+    text.windows(phrase.len())
+        .enumerate()
+        .filter(|(_, window)| *window == phrase)
+        .map(|(start, _)| (start, start + phrase.len() - 1))
+        .collect()
+}
+
+/// Words between two spans: 1 for neighbours, 0 when they overlap.
+fn gap(a: (usize, usize), b: (usize, usize)) -> usize {
+    b.0.saturating_sub(a.1).max(a.0.saturating_sub(b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(w: &str) -> Query {
+        Query::Words(vec![w.to_string()])
+    }
+
+    fn matches(query: &str, text: &str) -> bool {
+        parse_query(query).unwrap().matches(&words(text))
+    }
+
+    #[test]
+    fn parses_precedence_and_implicit_and() {
+        assert_eq!(
+            parse_query("faith AND works NOT dead").unwrap(),
+            Query::And(vec![
+                word("faith"),
+                word("works"),
+                Query::Not(Box::new(word("dead")))
+            ])
+        );
+        assert_eq!(
+            parse_query("love OR faith hope").unwrap(),
+            Query::Or(vec![
+                word("love"),
+                Query::And(vec![word("faith"), word("hope")])
+            ])
+        );
+        assert_eq!(
+            parse_query("\"Born again\" NEAR/3 spirit").unwrap(),
+            Query::Near(
+                vec!["born".to_string(), "again".to_string()],
+                vec!["spirit".to_string()],
+                3
+            )
+        );
+        // Lowercase operators are plain words.
+        assert_eq!(
+            parse_query("faith and").unwrap(),
+            Query::And(vec![word("faith"), word("and")])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        for bad in [
+            "",
+            "faith AND",
+            "(faith",
+            "faith)",
+            "\"born again",
+            "a NEAR/x b",
+            "(a OR b) NEAR c",
+            "a NEAR b NEAR c",
+            "--",
+        ] {
+            assert!(parse_query(bad).is_err(), "{:?} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn evaluates_against_verse_words() {
+        let text = "Even so faith, if it hath not works, is dead, being alone.";
+        assert!(matches("faith AND works", text));
+        assert!(!matches("faith AND works NOT dead", text));
+        assert!(matches("faith (dead OR alive)", text));
+        assert!(matches("\"is dead\"", text));
+        assert!(!matches("\"dead is\"", text));
+        assert!(matches("faith NEAR/5 works", text));
+        assert_eq!(
+            parse_query("faith (\"is dead\" OR alive) NOT works")
+                .unwrap()
+                .terms(),
+            vec!["faith", "is", "dead", "alive"]
+        );
+        assert!(!matches("faith NEAR/3 works", text));
+        assert!(matches("works NEAR/5 faith", text));
+        // Whole words only.
+        assert!(!matches("wor", text));
+    }
+}
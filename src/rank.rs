@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use crate::index::word_count;
+use crate::verses::Verse;
+
+/// BM25 term-frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 length normalization.
+const B: f64 = 0.75;
+
+/// One scoring term: how often it occurs in a verse's text, and how many verses
+/// of the translation contain it.
+pub struct Term<'a> {
+    pub frequency: Box<dyn Fn(&str) -> usize + 'a>,
+    pub verses_with: usize,
+}
+
+/// Okapi BM25 over a translation: rare terms weigh more than common ones, and a
+/// match in a short verse outranks the same match in a long one.
+pub struct Bm25 {
+    verses: usize,
+    average_len: f64,
+}
+
+impl Bm25 {
+    pub fn new(corpus: &[Verse]) -> Self {
+        let words: usize = corpus.iter().map(|v| word_count(&v.text)).sum();
+        Self {
+            verses: corpus.len(),
+            average_len: words as f64 / corpus.len().max(1) as f64,
+        }
+    }
+
+    pub fn score(&self, text: &str, terms: &[Term]) -> f64 {
+        let len = word_count(text) as f64;
+        let norm = K1 * (1.0 - B + B * len / self.average_len.max(1.0));
+        terms
+            .iter()
+            .map(|term| {
+                let tf = (term.frequency)(text) as f64;
+                if tf == 0.0 {
+                    return 0.0;
+                }
+                let df = term.verses_with as f64;
+                let idf = ((self.verses as f64 - df + 0.5) / (df + 0.5) + 1.0).ln();
+                idf * tf * (K1 + 1.0) / (tf + norm)
+            })
+            .sum()
+    }
+}
+
+/// TF-IDF weights over a corpus of tokenized verses, for comparing verses by
+/// wording: words shared by few verses count for more than common ones.
+pub struct TfIdf {
+    idf: HashMap<String, f64>,
+    verses: f64,
+}
+
+impl TfIdf {
+    pub fn new(docs: &[Vec<String>]) -> Self {
+        let mut verses_with: HashMap<&str, usize> = HashMap::new();
+        for doc in docs {
+            let mut seen: Vec<&str> = doc.iter().map(String::as_str).collect();
+            seen.sort_unstable();
+            seen.dedup();
+            for term in seen {
+                *verses_with.entry(term).or_default() += 1;
+            }
+        }
+        let n = docs.len() as f64;
+        let idf = verses_with
+            .into_iter()
+            .map(|(term, df)| (term.to_string(), (n / df as f64).ln() + 1.0))
+            .collect();
+        Self { idf, verses: n }
+    }
+
+    /// How much a term distinguishes a verse; a term the corpus lacks weighs
+    /// like one found in a single verse.
+    pub fn idf(&self, term: &str) -> f64 {
+        self.idf.get(term).copied().unwrap_or_else(|| self.rarest())
+    }
+
+    /// The weight of a term found in a single verse, the most any term has.
+    pub fn rarest(&self) -> f64 {
+        self.verses.max(1.0).ln() + 1.0
+    }
+
+    /// Whether a term occurs in more than one verse in ten: a function word
+    /// such as `the` or `and`, which matters little to similarity.
+    pub fn is_common(&self, term: &str) -> bool {
+        self.idf
+            .get(term)
+            .is_some_and(|idf| *idf <= 10f64.ln() + 1.0)
+    }
+
+    /// A document's unit-length weight vector: log term frequency times IDF.
+    pub fn weights<'a>(&self, doc: &'a [String]) -> HashMap<&'a str, f64> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for term in doc {
+            *counts.entry(term).or_default() += 1;
+        }
+        let mut weights: HashMap<&str, f64> = counts
+            .into_iter()
+            .map(|(term, tf)| (term, (1.0 + (tf as f64).ln()) * self.idf(term)))
+            .collect();
+        let norm = weights.values().map(|w| w * w).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            weights.values_mut().for_each(|w| *w /= norm);
+        }
+        weights
+    }
+}
+
+/// Cosine similarity of two unit-length weight vectors, from 0 to 1.
+pub fn cosine(a: &HashMap<&str, f64>, b: &HashMap<&str, f64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .iter()
+        .filter_map(|(term, w)| large.get(term).map(|v| w * v))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term<'a>(word: &'a str, verses_with: usize) -> Term<'a> {
+        Term {
+            frequency: Box::new(move |text: &str| {
+                crate::index::words(text)
+                    .iter()
+                    .filter(|w| *w == word)
+                    .count()
+            }),
+            verses_with,
+        }
+    }
+
+    #[test]
+    fn favours_rare_terms_repeats_and_short_verses() {
+        let corpus: Vec<Verse> = (0..10)
+            .map(|_| Verse::new("John", 1, 1, "one two three four"))
+            .collect();
+        let bm25 = Bm25::new(&corpus);
+        let rare = bm25.score("love one", &[term("love", 1)]);
+        let common = bm25.score("love one", &[term("love", 9)]);
+        assert!(rare > common);
+        let twice = bm25.score("love love", &[term("love", 1)]);
+        assert!(twice > rare);
+        let long = bm25.score("love one two three four five six", &[term("love", 1)]);
+        assert!(rare > long);
+        assert_eq!(bm25.score("one two", &[term("love", 1)]), 0.0);
+    }
+
+    #[test]
+    fn tf_idf_weighs_shared_rare_words() {
+        let docs: Vec<Vec<String>> = [
+            "in the beginning was the word",
+            "the word was made flesh",
+            "and the earth was without form",
+            "in the beginning god created the heaven",
+        ]
+        .iter()
+        .map(|text| crate::index::words(text))
+        .collect();
+        let tfidf = TfIdf::new(&docs);
+        let query = tfidf.weights(&docs[0]);
+        let score = |i: usize| cosine(&query, &tfidf.weights(&docs[i]));
+        assert!((score(0) - 1.0).abs() < 1e-9);
+        // "in the beginning" outweighs "the ... was".
+        assert!(score(3) > score(2));
+        assert!(score(1) > score(2));
+    }
+}
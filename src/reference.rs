@@ -1,29 +1,300 @@
 use anyhow::{bail, Result};
+use clap::ValueEnum;
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
 
-use crate::books::normalize_book;
+use crate::books::{
+    book_from_osis, book_position, normalize_book, osis_code, suggest_books, usfm_code, BOOKS,
+};
+use crate::locales::{Locale, LOCALES};
+use crate::text::join_or;
 
 /// A parsed scripture reference. Depending on which fields are set it can denote
-/// a whole book, a whole chapter, a single verse, a verse range, or an explicit
-/// list of verses.
+/// a whole book, a whole chapter, a single verse, a verse range, an explicit
+/// list of verses, or a range spanning chapters or books.
 ///
-/// - whole book:     `chapter = None`
+/// - whole book:     `chapter = None`, `end = None`
 /// - whole chapter:  `chapter = Some(c)`, `verse = None`
 /// - single verse:   `verse = Some(v)`, `verse_end = None`, `verse_list` empty
 /// - verse range:    `verse = Some(start)`, `verse_end = Some(end)`
 /// - explicit list:  `verse_list` non-empty (`verse` holds the first for callers
 ///   that only understand a single anchor verse)
+/// - wider range:    `end = Some(..)` (`John 3:16-4:2`, `Genesis 1-3`,
+///   `Genesis 50-Exodus 2`); the start fields hold the first verse or chapter
+/// - following:      `following = true` (`Psalm 119:105ff`), from `verse` to
+///   the end of the chapter
+///
+/// Half-verse markers (`Romans 3:23a`, `John 1:1b-3`) do not change which verses
+/// are selected; they are carried along so output can report them.
 pub struct ReferenceQuery {
     pub book: String,
     pub chapter: Option<u16>,
     pub verse: Option<u16>,
     pub verse_end: Option<u16>,
     pub verse_list: Vec<u16>,
+    pub end: Option<RangeEnd>,
+    /// Half-verse marker (`a`, `b`, `c`) on the first verse.
+    pub verse_part: Option<char>,
+    /// Half-verse marker on the last verse of a range (`John 1:1-3a`).
+    pub verse_end_part: Option<char>,
+    pub following: bool,
+}
+
+/// The far end of a range that leaves its starting chapter. A missing chapter
+/// runs to the end of the book, a missing verse to the end of the chapter.
+pub struct RangeEnd {
+    pub book: String,
+    pub chapter: Option<u16>,
+    pub verse: Option<u16>,
+}
+
+impl ReferenceQuery {
+    /// A bare book name: no chapter and no range.
+    pub fn is_whole_book(&self) -> bool {
+        self.chapter.is_none() && self.end.is_none()
+    }
+
+    /// The half-verse marker, if any, on the verse at `index` of `count`
+    /// verses this reference resolved to.
+    pub fn part_at(&self, index: usize, count: usize) -> Option<char> {
+        if index == 0 && self.verse_part.is_some() {
+            self.verse_part
+        } else if index + 1 == count {
+            self.verse_end_part
+        } else {
+            None
+        }
+    }
 }
 
+impl fmt::Display for ReferenceQuery {
+    /// Normalized form with canonical book names, e.g. `John 3:16-4:2`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.styled(RefStyle::Full))
+    }
+}
+
+/// How references are written out. Verse lists collapse into minimal ranges in
+/// every style (`16,17,18,20` -> `16-18, 20`).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum RefStyle {
+    /// Full book names (`1 Corinthians 13:4`).
+    #[default]
+    Full,
+    /// SBL Handbook abbreviations (`1 Cor 13:4`).
+    Sbl,
+    /// Compact three-letter codes (`1Co 13:4`).
+    Short,
+    /// OSIS ids (`1Cor.13.4`, ranges as `1Cor.13.4-1Cor.13.7`).
+    Osis,
+}
+
+impl RefStyle {
+    /// A canonical book name in this style. Names outside `BOOKS` are kept as
+    /// is, and full names are written in `locale`'s language when one is given;
+    /// the abbreviated styles are standard codes and stay as they are.
+    pub fn book(self, name: &str, locale: Option<&Locale>) -> String {
+        if book_position(name).is_none() {
+            return name.to_string();
+        }
+        match self {
+            RefStyle::Full => match locale {
+                Some(locale) => locale.book_name(name).to_string(),
+                None => name.to_string(),
+            },
+            RefStyle::Osis => osis_code(name).to_string(),
+            // SBL abbreviations are the OSIS codes with a space after a leading
+            // numeral (`1Cor` -> `1 Cor`).
+            RefStyle::Sbl => {
+                let code = osis_code(name);
+                match code.strip_prefix(|c: char| c.is_ascii_digit()) {
+                    Some(rest) => format!("{} {}", &code[..1], rest),
+                    None => code.to_string(),
+                }
+            }
+            // The USFM code in title case (`1CO` -> `1Co`, `JHN` -> `Jhn`).
+            RefStyle::Short => {
+                let mut out = String::new();
+                let mut seen_letter = false;
+                for c in usfm_code(name).chars() {
+                    if c.is_ascii_alphabetic() && seen_letter {
+                        out.push(c.to_ascii_lowercase());
+                    } else {
+                        out.push(c);
+                        seen_letter |= c.is_ascii_alphabetic();
+                    }
+                }
+                out
+            }
+        }
+    }
+
+    /// A single verse in this style (`John 3:16`, `John.3.16`), localized as in
+    /// `book`.
+    pub fn verse(self, book: &str, chapter: u16, verse: u16, locale: Option<&Locale>) -> String {
+        let book = self.book(book, locale);
+        match self {
+            RefStyle::Osis => format!("{}.{}.{}", book, chapter, verse),
+            _ => format!("{} {}:{}", book, chapter, verse),
+        }
+    }
+}
+
+impl ReferenceQuery {
+    /// The reference written in `style`, with book names in that style and verse
+    /// lists collapsed into minimal ranges.
+    pub fn styled(&self, style: RefStyle) -> String {
+        self.styled_in(style, None)
+    }
+
+    /// Like `styled`, with book names localized as in `RefStyle::book`.
+    pub fn styled_in(&self, style: RefStyle, locale: Option<&Locale>) -> String {
+        if style == RefStyle::Osis {
+            return self.osis_styled();
+        }
+        let mut out = style.book(&self.book, locale);
+        if let Some(chapter) = self.chapter {
+            out.push_str(&format!(" {}", chapter));
+        }
+        if !self.verse_list.is_empty() {
+            let runs: Vec<String> = verse_runs(&self.verse_list)
+                .into_iter()
+                .map(|(first, last)| {
+                    if first == last {
+                        first.to_string()
+                    } else {
+                        format!("{}-{}", first, last)
+                    }
+                })
+                .collect();
+            out.push_str(&format!(":{}", runs.join(", ")));
+        } else if let Some(verse) = self.verse {
+            out.push_str(&format!(":{}", verse));
+            out.extend(self.verse_part);
+            if self.following {
+                out.push_str("ff");
+            }
+            if let Some(end) = self.verse_end {
+                out.push_str(&format!("-{}", end));
+                out.extend(self.verse_end_part);
+            }
+        }
+        let Some(end) = &self.end else {
+            return out;
+        };
+        out.push('-');
+        // Repeat the book when it changes, or when a bare chapter number would
+        // otherwise read as a verse (`John 3:16-John 4`).
+        let repeat_book = end.book != self.book || (self.verse.is_some() && end.verse.is_none());
+        if repeat_book {
+            out.push_str(&style.book(&end.book, locale));
+            if let Some(chapter) = end.chapter {
+                out.push_str(&format!(" {}", chapter));
+            }
+        } else if let Some(chapter) = end.chapter {
+            out.push_str(&chapter.to_string());
+        }
+        if let Some(verse) = end.verse {
+            out.push_str(&format!(":{}", verse));
+            out.extend(self.verse_end_part);
+        }
+        out
+    }
+
+    /// OSIS form: every end of a range is a full id, and a verse list becomes
+    /// space-separated ids and ranges (`John.3.16-John.3.18 John.3.20`).
+    fn osis_styled(&self) -> String {
+        let code = osis_code(&self.book);
+        let id = |chapter: u16, verse: u16| format!("{}.{}.{}", code, chapter, verse);
+        let Some(chapter) = self.chapter else {
+            return match &self.end {
+                Some(end) => format!("{}-{}", code, osis_end(end)),
+                None => code.to_string(),
+            };
+        };
+        if !self.verse_list.is_empty() {
+            let runs: Vec<String> = verse_runs(&self.verse_list)
+                .into_iter()
+                .map(|(first, last)| {
+                    if first == last {
+                        id(chapter, first)
+                    } else {
+                        format!("{}-{}", id(chapter, first), id(chapter, last))
+                    }
+                })
+                .collect();
+            return runs.join(" ");
+        }
+        // Half-verse markers use the OSIS grain suffix (`Rom.3.23!a`).
+        let grain = |part: Option<char>| part.map(|p| format!("!{}", p)).unwrap_or_default();
+        let start = match self.verse {
+            Some(verse) => format!("{}{}", id(chapter, verse), grain(self.verse_part)),
+            None => format!("{}.{}", code, chapter),
+        };
+        let end_part = grain(self.verse_end_part);
+        match (&self.end, self.verse.zip(self.verse_end)) {
+            (Some(end), _) => format!("{}-{}{}", start, osis_end(end), end_part),
+            (None, Some((_, verse_end))) => {
+                format!("{}-{}{}", start, id(chapter, verse_end), end_part)
+            }
+            // `ff` is a range to the end of the chapter.
+            (None, None) if self.following => format!("{}-{}.{}", start, code, chapter),
+            (None, None) => start,
+        }
+    }
+}
+
+fn osis_end(end: &RangeEnd) -> String {
+    let mut out = osis_code(&end.book).to_string();
+    for n in [end.chapter, end.verse].into_iter().flatten() {
+        out.push_str(&format!(".{}", n));
+    }
+    out
+}
+
+/// Sorted, deduplicated verse numbers grouped into runs of consecutive verses,
+/// as `(first, last)` pairs.
+fn verse_runs(verses: &[u16]) -> Vec<(u16, u16)> {
+    let mut sorted = verses.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut runs: Vec<(u16, u16)> = Vec::new();
+    for verse in sorted {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == verse => *last = verse,
+            _ => runs.push((verse, verse)),
+        }
+    }
+    runs
+}
+
+#[derive(Default)]
 struct VerseSpec {
     verse: Option<u16>,
     verse_end: Option<u16>,
     list: Vec<u16>,
+    part: Option<char>,
+    end_part: Option<char>,
+    following: bool,
+}
+
+/// Resolve a book name or alias to its canonical name. An unknown name is an
+/// error that suggests the closest books (`Unknown book: Jhon. Did you mean John?`).
+pub fn lookup_book(input: &str) -> Result<&'static str> {
+    if let Some(book) = normalize_book(input) {
+        return Ok(book);
+    }
+    let input = input.trim();
+    let suggestions = suggest_books(input);
+    if suggestions.is_empty() {
+        bail!("Unknown book: {}", input);
+    }
+    bail!(
+        "Unknown book: {}. Did you mean {}?",
+        input,
+        join_or(&suggestions)
+    )
 }
 
 pub fn parse_reference(tokens: &[String]) -> Result<ReferenceQuery> {
@@ -31,7 +302,58 @@ pub fn parse_reference(tokens: &[String]) -> Result<ReferenceQuery> {
         bail!("Reference is required");
     }
 
+    let joined = tokens.join(" ").replace(['\u{2013}', '\u{2014}'], "-");
+    if let Some(query) = parse_verse_ids(joined.trim()) {
+        return query;
+    }
+    // A comma list stays within one chapter, so its dashes are verse ranges
+    // (`John 3:16-18,20`). Otherwise a dash separates the two ends of a range.
+    if !joined.contains(',') {
+        if let Some((left, right)) = joined.split_once('-') {
+            return parse_span(&joined, left, right);
+        }
+    }
+    parse_single(&joined)
+}
+
+/// Parse a semicolon-separated list of passages (`John 3:16; Rom 8:28-30; Ps 23`).
+/// A passage of bare numbers (`Rom 8:28; 12:1-2`) keeps the previous book.
+pub fn parse_references(tokens: &[String]) -> Result<Vec<ReferenceQuery>> {
     let joined = tokens.join(" ");
+    let mut out: Vec<ReferenceQuery> = Vec::new();
+    for part in joined.split(';') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        // Verse numbers may carry half-verse or `f`/`ff` suffixes (`5:8b`).
+        let bare = part.starts_with(|c: char| c.is_ascii_digit())
+            && part.chars().all(|c| {
+                c.is_ascii_digit()
+                    || c.is_whitespace()
+                    || matches!(c, ':' | '-' | ',' | 'a' | 'b' | 'c' | 'f')
+            })
+            && parse_verse_ids(part).is_none();
+        let text = if bare {
+            let Some(prev) = out.last() else {
+                bail!("Book is required: {}", part);
+            };
+            // After a cross-book range, the range's last book carries over.
+            let book = prev.end.as_ref().map_or(&prev.book, |end| &end.book);
+            format!("{} {}", book, part)
+        } else {
+            part.to_string()
+        };
+        out.push(parse_reference(&[text])?);
+    }
+    if out.is_empty() {
+        bail!("Reference is required");
+    }
+    Ok(out)
+}
+
+/// Parse a reference without a range dash (a comma list may contain ranges).
+fn parse_single(joined: &str) -> Result<ReferenceQuery> {
     let (book_part, chapter, spec) = if joined.contains(':') {
         let parts: Vec<&str> = joined.split(':').collect();
         if parts.len() != 2 {
@@ -44,24 +366,184 @@ pub fn parse_reference(tokens: &[String]) -> Result<ReferenceQuery> {
         let (book_part, chapter) = split_book_and_chapter(left)?;
         (book_part, Some(chapter), Some(spec))
     } else {
-        split_trailing_numbers(&joined)?
+        split_trailing_numbers(joined)?
     };
 
-    let book =
-        normalize_book(&book_part).ok_or_else(|| anyhow::anyhow!("Unknown book: {}", book_part))?;
+    let book = lookup_book(&book_part)?;
+
+    let spec = spec.unwrap_or_default();
+    Ok(ReferenceQuery {
+        book: book.to_string(),
+        chapter,
+        verse: spec.verse,
+        verse_end: spec.verse_end,
+        verse_list: spec.list,
+        end: None,
+        verse_part: spec.part,
+        verse_end_part: spec.end_part,
+        following: spec.following,
+    })
+}
 
-    let (verse, verse_end, verse_list) = match spec {
-        Some(spec) => (spec.verse, spec.verse_end, spec.list),
-        None => (None, None, Vec::new()),
+/// Parse `left-right`, where `right` is a verse (`John 3:16-18`), a chapter
+/// (`Genesis 1-3`), a chapter and verse (`John 3:16-4:2`), or a reference in
+/// another book (`Genesis 50-Exodus 2:3`).
+fn parse_span(joined: &str, left: &str, right: &str) -> Result<ReferenceQuery> {
+    let mut start = parse_single(left.trim())?;
+    let (right, end_part) = split_verse_part(right.trim());
+    if right.is_empty() || start.following {
+        bail!("Invalid reference: {}", joined);
+    }
+    start.verse_end_part = end_part;
+
+    let numeric = right
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == ':' || c.is_whitespace());
+    let end = if numeric {
+        let nums: Vec<u16> = right
+            .split(|c: char| c == ':' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(parse_u16)
+            .collect::<Option<_>>()
+            .ok_or_else(|| anyhow::anyhow!("Invalid reference: {}", joined))?;
+        let (chapter, verse) = match (nums.as_slice(), start.chapter, start.verse) {
+            // A lone number after a verse is a verse in the same chapter.
+            ([verse], Some(chapter), Some(_)) => (chapter, Some(*verse)),
+            ([chapter], Some(_), None) => (*chapter, None),
+            ([chapter, verse], Some(_), _) => (*chapter, Some(*verse)),
+            _ => bail!("Invalid reference: {}", joined),
+        };
+        RangeEnd {
+            book: start.book.clone(),
+            chapter: Some(chapter),
+            verse,
+        }
+    } else {
+        let end = parse_single(right)?;
+        if end.verse_end.is_some() || !end.verse_list.is_empty() || end.following {
+            bail!("Invalid reference: {}", joined);
+        }
+        RangeEnd {
+            book: end.book,
+            chapter: end.chapter,
+            verse: end.verse,
+        }
     };
 
-    Ok(ReferenceQuery {
+    join_span(joined, start, end)
+}
+
+/// Attach `end` to `start`, collapsing a range that stays within one chapter to
+/// the verse-range form and rejecting one that runs backwards.
+fn join_span(joined: &str, mut start: ReferenceQuery, end: RangeEnd) -> Result<ReferenceQuery> {
+    if end.book == start.book && end.chapter.is_some() && end.chapter == start.chapter {
+        match end.verse {
+            Some(verse) => {
+                let first = start.verse.unwrap_or(1);
+                if verse < first {
+                    bail!("Invalid range: {}", joined);
+                }
+                start.verse = Some(first);
+                start.verse_end = Some(verse);
+            }
+            // `John 3:16-John 3` (OSIS `John.3.16-John.3`): to the end of the chapter.
+            None if start.verse.is_some() => start.following = true,
+            None => {}
+        }
+        return Ok(start);
+    }
+
+    let first = (
+        book_position(&start.book),
+        start.chapter.unwrap_or(0),
+        start.verse.unwrap_or(0),
+    );
+    let last = (
+        book_position(&end.book),
+        end.chapter.unwrap_or(u16::MAX),
+        end.verse.unwrap_or(u16::MAX),
+    );
+    if last < first {
+        bail!("Invalid range: {}", joined);
+    }
+    start.end = Some(end);
+    Ok(start)
+}
+
+/// Parse machine verse ids: OSIS (`John.3.16`, `Ps.23`, `Gen.1.1-Gen.2.3`) or
+/// integer `BBCCCVVV` (`43003016`, `1001001-1002003`). Returns `None` when the
+/// input is not shaped like an id, so it can be parsed as a human reference.
+fn parse_verse_ids(input: &str) -> Option<Result<ReferenceQuery>> {
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return None;
+    }
+    let (left, right) = match input.split_once('-') {
+        Some((left, right)) => (left, Some(right)),
+        None => (input, None),
+    };
+    let start = parse_verse_id(left)?;
+    let Some(right) = right else {
+        return Some(start);
+    };
+    Some(start.and_then(|start| {
+        let end = parse_verse_id(right)
+            .unwrap_or_else(|| Err(anyhow::anyhow!("Invalid verse id: {}", right)))?;
+        let mut start = start;
+        start.verse_end_part = end.verse_part;
+        let end = RangeEnd {
+            book: end.book,
+            chapter: end.chapter,
+            verse: end.verse,
+        };
+        join_span(input, start, end)
+    }))
+}
+
+fn parse_verse_id(id: &str) -> Option<Result<ReferenceQuery>> {
+    // An OSIS grain marks a half verse (`Rom.3.23!a`).
+    let (id, verse_part) = match id.split_once('!') {
+        Some((id, grain @ ("a" | "b" | "c"))) => (id, grain.chars().next()),
+        Some(_) => return Some(Err(anyhow::anyhow!("Invalid verse id: {}", id))),
+        None => (id, None),
+    };
+    let (book, chapter, verse) = if id.len() >= 7 && id.chars().all(|c| c.is_ascii_digit()) {
+        let n: u32 = id.parse().ok()?;
+        let book = (n / 1_000_000) as usize;
+        let Some(def) = book.checked_sub(1).and_then(|i| BOOKS.get(i)) else {
+            return Some(Err(anyhow::anyhow!("Invalid verse id: {}", id)));
+        };
+        (
+            def.name,
+            Some((n / 1000 % 1000) as u16),
+            Some((n % 1000) as u16),
+        )
+    } else {
+        let mut parts = id.split('.');
+        let book = book_from_osis(parts.next()?)?;
+        let nums: Vec<&str> = parts.collect();
+        if nums.is_empty() || nums.len() > 2 {
+            return None;
+        }
+        let nums: Option<Vec<u16>> = nums.iter().map(|n| parse_u16(n)).collect();
+        let Some(nums) = nums else {
+            return Some(Err(anyhow::anyhow!("Invalid verse id: {}", id)));
+        };
+        (book, nums.first().copied(), nums.get(1).copied())
+    };
+    if chapter == Some(0) || verse == Some(0) || (verse.is_none() && verse_part.is_some()) {
+        return Some(Err(anyhow::anyhow!("Invalid verse id: {}", id)));
+    }
+    Some(Ok(ReferenceQuery {
         book: book.to_string(),
         chapter,
         verse,
-        verse_end,
-        verse_list,
-    })
+        verse_end: None,
+        verse_list: Vec::new(),
+        end: None,
+        verse_part,
+        verse_end_part: None,
+        following: false,
+    }))
 }
 
 fn split_book_and_chapter(input: &str) -> Result<(String, u16)> {
@@ -93,8 +575,7 @@ fn split_trailing_numbers(input: &str) -> Result<(String, Option<u16>, Option<Ve
                 if let Some(prev_num) = parse_u16(parts[parts.len() - 2]) {
                     spec = Some(VerseSpec {
                         verse: Some(last_num),
-                        verse_end: None,
-                        list: Vec::new(),
+                        ..VerseSpec::default()
                     });
                     chapter = Some(prev_num);
                     book_parts = parts[..parts.len() - 2].to_vec();
@@ -128,7 +609,9 @@ fn split_trailing_numbers(input: &str) -> Result<(String, Option<u16>, Option<Ve
 
 /// Parse the portion after the chapter into a verse selector: a single verse
 /// (`16`), a range (`16-18`), or a comma list which may itself contain ranges
-/// (`16,18,20` or `16-18,20`).
+/// (`16,18,20` or `16-18,20`). A single verse or either end of a range may carry
+/// a half-verse marker (`23a`, `1b-3`), and a single verse may be followed by
+/// `f` (and the next verse) or `ff` (and the rest of the chapter).
 fn parse_verse_spec(input: &str) -> Option<VerseSpec> {
     let input = input.trim();
     if input.is_empty() {
@@ -156,29 +639,62 @@ fn parse_verse_spec(input: &str) -> Option<VerseSpec> {
         }
         return Some(VerseSpec {
             verse: list.first().copied(),
-            verse_end: None,
             list,
+            ..VerseSpec::default()
         });
     }
 
-    if let Some((start, end)) = parse_range(input) {
+    if let Some((start, end)) = input.split_once('-') {
+        let (start, part) = split_verse_part(start.trim());
+        let (end, end_part) = split_verse_part(end.trim());
+        let (start, end) = (parse_u16(start)?, parse_u16(end)?);
         if end < start {
             return None;
         }
         return Some(VerseSpec {
             verse: Some(start),
             verse_end: Some(end),
-            list: Vec::new(),
+            part,
+            end_part,
+            ..VerseSpec::default()
         });
     }
 
+    if let Some(verse) = input.strip_suffix("ff") {
+        return Some(VerseSpec {
+            verse: Some(parse_u16(verse)?),
+            following: true,
+            ..VerseSpec::default()
+        });
+    }
+    if let Some(verse) = input.strip_suffix('f') {
+        let verse = parse_u16(verse)?;
+        return Some(VerseSpec {
+            verse: Some(verse),
+            verse_end: Some(verse.checked_add(1)?),
+            ..VerseSpec::default()
+        });
+    }
+    let (verse, part) = split_verse_part(input);
     Some(VerseSpec {
-        verse: Some(parse_u16(input)?),
-        verse_end: None,
-        list: Vec::new(),
+        verse: Some(parse_u16(verse)?),
+        part,
+        ..VerseSpec::default()
     })
 }
 
+/// Split a trailing half-verse marker off a verse number (`23a` -> `23`, `a`).
+/// Only a letter right after a digit counts, so book names are left alone.
+fn split_verse_part(input: &str) -> (&str, Option<char>) {
+    let mut chars = input.chars();
+    let last = chars.next_back().map(|c| c.to_ascii_lowercase());
+    let digit_before = chars.next_back().is_some_and(|c| c.is_ascii_digit());
+    match last {
+        Some(part @ ('a' | 'b' | 'c')) if digit_before => (&input[..input.len() - 1], Some(part)),
+        _ => (input, None),
+    }
+}
+
 fn parse_range(input: &str) -> Option<(u16, u16)> {
     let (a, b) = input.split_once('-')?;
     Some((parse_u16(a.trim())?, parse_u16(b.trim())?))
@@ -188,6 +704,167 @@ fn parse_u16(input: &str) -> Option<u16> {
     input.parse::<u16>().ok()
 }
 
+/// A scripture reference found in free text by `find_references`.
+pub struct FoundReference {
+    /// Byte offsets of the match within the scanned text.
+    pub start: usize,
+    pub end: usize,
+    /// The reference as written (`Rom. 8:28`, or `12:1` after a semicolon).
+    pub text: String,
+    /// The parsed reference, or why the match does not parse.
+    pub query: Result<ReferenceQuery>,
+}
+
+/// Find every scripture reference in prose or Markdown, in text order. A match
+/// is a book name or alias followed by a chapter, optionally with verses,
+/// ranges (including into another book), comma lists, and `;`-separated
+/// chapter:verse continuations that keep the book.
+///
+/// To avoid matching ordinary words, a book written with letters must be
+/// capitalized, and one- or two-letter aliases (`Is`, `Am`) need a verse.
+pub fn find_references(text: &str) -> Vec<FoundReference> {
+    let re = reference_regex();
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while let Some(caps) = re.captures_at(text, pos) {
+        let m = caps.get(0).expect("group 0 always matches");
+        let book = caps.name("book").expect("book group always matches");
+        let nums = caps.name("nums").expect("nums group always matches");
+
+        let first = book.as_str().chars().next().unwrap_or(' ');
+        let short = book
+            .as_str()
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .count()
+            <= 2;
+        let glued = text[m.end()..].starts_with(char::is_alphanumeric);
+        if first.is_lowercase() || (short && !nums.as_str().contains(':')) || glued {
+            pos = book.end();
+            continue;
+        }
+
+        let mut end = m.end();
+        // A range into another book: `Genesis 50-Exodus 2`.
+        if let Some(dash) = dash_regex().find(&text[end..]) {
+            let at = end + dash.end();
+            if let Some(next) = re.find_at(text, at).filter(|next| next.start() == at) {
+                end = next.end();
+            }
+        }
+        // A comma list after a verse, stopping where a new reference begins
+        // (`Rom 8:28, 1 Cor 13`).
+        if nums.as_str().contains(':') {
+            while let Some(item) = list_regex().find(&text[end..]) {
+                let num_start = end
+                    + item
+                        .as_str()
+                        .find(|c: char| c.is_ascii_digit())
+                        .unwrap_or(0);
+                if re
+                    .find_at(text, num_start)
+                    .is_some_and(|next| next.start() == num_start)
+                {
+                    break;
+                }
+                end += item.end();
+            }
+        }
+
+        let written = &text[m.start()..end];
+        let query = parse_reference(&[written.to_string()]);
+        let mut carried = query.as_ref().ok().map(|q| q.book.clone());
+        out.push(FoundReference {
+            start: m.start(),
+            end,
+            text: written.to_string(),
+            query,
+        });
+
+        // `Rom 8:28; 12:1-2`: bare chapter:verse continuations keep the book.
+        while let Some(book) = carried.take() {
+            let Some(next) = continuation_regex().captures(&text[end..]) else {
+                break;
+            };
+            let nums = next.name("nums").expect("nums group always matches");
+            let (start, stop) = (end + nums.start(), end + nums.end());
+            if text[stop..].starts_with(char::is_alphanumeric) {
+                break;
+            }
+            let query = parse_reference(&[format!("{} {}", book, nums.as_str())]);
+            carried = query.as_ref().ok().map(|q| q.book.clone());
+            out.push(FoundReference {
+                start,
+                end: stop,
+                text: nums.as_str().to_string(),
+                query,
+            });
+            end = stop;
+        }
+        pos = end;
+    }
+    out
+}
+
+/// Chapter, optional verse, and optional same-book range end. A verse may carry
+/// a half-verse marker or `f`/`ff`, the range end a half-verse marker.
+const NUMS_PATTERN: &str = concat!(
+    r"\d{1,3}(?:\s*:\s*\d{1,3}(?:(?-i:[abc]|ff?)\b)?)?",
+    r"(?:\s*[-\u{2013}]\s*\d{1,3}(?:\s*:\s*\d{1,3})?(?:(?-i:[abc])\b)?)?"
+);
+
+/// Book name or alias (longest first, so `Song of Solomon` beats `Song`)
+/// followed by chapter and verse numbers.
+fn reference_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        // English names and aliases, plus localized full names (their short
+        // aliases are too easily confused with ordinary words in prose).
+        let mut names: Vec<&str> = BOOKS
+            .iter()
+            .flat_map(|b| std::iter::once(b.name).chain(b.aliases.iter().copied()))
+            .chain(
+                LOCALES
+                    .iter()
+                    .flat_map(|l| l.books.iter().map(|(name, _)| *name)),
+            )
+            .collect();
+        names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+        names.dedup();
+        let alternation: Vec<String> = names
+            .iter()
+            .map(|n| regex::escape(n).replace(' ', r"\s+"))
+            .collect();
+        let pattern = format!(
+            r"(?i)\b(?P<book>{})\b\.?\s*(?P<nums>{})",
+            alternation.join("|"),
+            NUMS_PATTERN
+        );
+        Regex::new(&pattern).expect("reference pattern is valid")
+    })
+}
+
+fn dash_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\s*[-\u{2013}]\s*").expect("dash pattern is valid"))
+}
+
+fn list_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\s*,\s*\d{1,3}(?:\s*[-\u{2013}]\s*\d{1,3})?(?:\b|$)")
+            .expect("list pattern is valid")
+    })
+}
+
+fn continuation_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\s*;\s*(?P<nums>\d{1,3}\s*:\s*\d{1,3}(?:\s*[-\u{2013}]\s*\d{1,3}(?:\s*:\s*\d{1,3})?)?)")
+            .expect("continuation pattern is valid")
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,4 +950,326 @@ mod tests {
         let owned = vec!["John".to_string(), "3:18-16".to_string()];
         assert!(parse_reference(&owned).is_err());
     }
+
+    #[test]
+    fn cross_chapter_range() {
+        let r = q(&["John", "3:16-4:2"]);
+        assert_eq!(r.book, "John");
+        assert_eq!(r.chapter, Some(3));
+        assert_eq!(r.verse, Some(16));
+        assert_eq!(r.verse_end, None);
+        let end = r.end.as_ref().unwrap();
+        assert_eq!(end.book, "John");
+        assert_eq!(end.chapter, Some(4));
+        assert_eq!(end.verse, Some(2));
+        assert_eq!(r.to_string(), "John 3:16-4:2");
+    }
+
+    #[test]
+    fn chapter_range() {
+        let r = q(&["Genesis", "1-3"]);
+        assert_eq!(r.chapter, Some(1));
+        assert_eq!(r.verse, None);
+        let end = r.end.as_ref().unwrap();
+        assert_eq!(end.chapter, Some(3));
+        assert_eq!(end.verse, None);
+        assert_eq!(r.to_string(), "Genesis 1-3");
+    }
+
+    #[test]
+    fn sermon_on_the_mount() {
+        let r = q(&["Matt", "5:1", "-", "7:29"]);
+        assert_eq!(r.book, "Matthew");
+        assert_eq!(r.to_string(), "Matthew 5:1-7:29");
+    }
+
+    #[test]
+    fn cross_book_range() {
+        let r = q(&["Genesis", "50:26-Exodus", "1:2"]);
+        assert_eq!(r.book, "Genesis");
+        let end = r.end.as_ref().unwrap();
+        assert_eq!(end.book, "Exodus");
+        assert_eq!(end.chapter, Some(1));
+        assert_eq!(end.verse, Some(2));
+        assert_eq!(r.to_string(), "Genesis 50:26-Exodus 1:2");
+        assert!(!r.is_whole_book());
+    }
+
+    #[test]
+    fn same_chapter_span_collapses_to_verse_range() {
+        let r = q(&["John", "3:16-3:18"]);
+        assert_eq!(r.verse, Some(16));
+        assert_eq!(r.verse_end, Some(18));
+        assert!(r.end.is_none());
+    }
+
+    #[test]
+    fn backwards_span_is_error() {
+        let owned = vec!["John 4:2-3:16".to_string()];
+        assert!(parse_reference(&owned).is_err());
+        let owned = vec!["Exodus 1-Genesis 2".to_string()];
+        assert!(parse_reference(&owned).is_err());
+    }
+
+    fn qs(input: &str) -> Vec<String> {
+        parse_references(&[input.to_string()])
+            .unwrap()
+            .iter()
+            .map(|r| r.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn multiple_passages() {
+        assert_eq!(
+            qs("John 3:16; Rom 8:28-30; Ps 23"),
+            vec!["John 3:16", "Romans 8:28-30", "Psalms 23"]
+        );
+    }
+
+    #[test]
+    fn bare_passage_keeps_previous_book() {
+        assert_eq!(
+            qs("Rom 8:28; 12:1-2; 1 Cor 13"),
+            vec!["Romans 8:28", "Romans 12:1-2", "1 Corinthians 13"]
+        );
+        assert_eq!(
+            qs("Genesis 50-Exodus 2; 3:14"),
+            vec!["Genesis 50-Exodus 2", "Exodus 3:14"]
+        );
+    }
+
+    #[test]
+    fn single_passage_and_stray_semicolons() {
+        assert_eq!(qs("John 3:16;"), vec!["John 3:16"]);
+        assert!(parse_references(&["12:1; Rom 8".to_string()]).is_err());
+        assert!(parse_references(&[";".to_string()]).is_err());
+    }
+
+    fn found(text: &str) -> Vec<(String, String)> {
+        find_references(text)
+            .into_iter()
+            .map(|f| {
+                assert_eq!(&text[f.start..f.end], f.text);
+                let normalized = f.query.map(|q| q.to_string()).unwrap_or_default();
+                (f.text, normalized)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_references_in_prose() {
+        let text = "Read John 3:16 first, then Rom. 8:28, 30 and 1 Cor 13:4-7.";
+        assert_eq!(
+            found(text),
+            vec![
+                ("John 3:16".to_string(), "John 3:16".to_string()),
+                ("Rom. 8:28, 30".to_string(), "Romans 8:28, 30".to_string()),
+                (
+                    "1 Cor 13:4-7".to_string(),
+                    "1 Corinthians 13:4-7".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn list_stops_at_next_reference() {
+        let refs = found("See Rom 8:28, 1 Cor 13 and Psalm 23.");
+        let written: Vec<&str> = refs.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(written, vec!["Rom 8:28", "1 Cor 13", "Psalm 23"]);
+    }
+
+    #[test]
+    fn semicolon_continuation_and_cross_book_range() {
+        let refs = found("Rom 8:28; 12:1-2 and Genesis 50:26-Exodus 1:2.");
+        assert_eq!(
+            refs,
+            vec![
+                ("Rom 8:28".to_string(), "Romans 8:28".to_string()),
+                ("12:1-2".to_string(), "Romans 12:1-2".to_string()),
+                (
+                    "Genesis 50:26-Exodus 1:2".to_string(),
+                    "Genesis 50:26-Exodus 1:2".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_verse_suffixes_in_prose() {
+        let refs = found("Compare Romans 3:23a with John 1:1b-3 and Ps 119:105ff.");
+        let normalized: Vec<&str> = refs.iter().map(|(_, n)| n.as_str()).collect();
+        assert_eq!(
+            normalized,
+            vec!["Romans 3:23a", "John 1:1b-3", "Psalms 119:105ff"]
+        );
+        assert!(found("John 3:16and more").is_empty());
+    }
+
+    #[test]
+    fn finds_localized_book_names() {
+        let refs = found("Lee Juan 3:16 y Römer 8:28; 1. Mose 1:1.");
+        let normalized: Vec<&str> = refs.iter().map(|(_, n)| n.as_str()).collect();
+        assert_eq!(normalized, vec!["John 3:16", "Romans 8:28", "Genesis 1:1"]);
+    }
+
+    #[test]
+    fn ignores_ordinary_words_and_numbers() {
+        assert!(found("This is 5 feet long; am 3 years old. Job 99x").is_empty());
+        assert!(found("The table Is 5 wide.").is_empty());
+        assert_eq!(found("Is 53:5").len(), 1);
+    }
+
+    #[test]
+    fn osis_ids() {
+        let r = q(&["John.3.16"]);
+        assert_eq!(r.to_string(), "John 3:16");
+        assert_eq!(q(&["1Cor.13.4"]).to_string(), "1 Corinthians 13:4");
+        assert_eq!(q(&["ps.23"]).to_string(), "Psalms 23");
+        assert_eq!(q(&["John.3.16-John.3.18"]).to_string(), "John 3:16-18");
+        let r = q(&["Gen.1.1-Gen.2.3"]);
+        assert_eq!(r.to_string(), "Genesis 1:1-2:3");
+        assert!(r.end.is_some());
+    }
+
+    #[test]
+    fn numeric_verse_ids() {
+        assert_eq!(q(&["43003016"]).to_string(), "John 3:16");
+        assert_eq!(q(&["1001001-1002003"]).to_string(), "Genesis 1:1-2:3");
+        assert_eq!(q(&["66022021"]).to_string(), "Revelation 22:21");
+        assert_eq!(
+            qs("John 3:16; 43003017-43003018"),
+            vec!["John 3:16", "John 3:17-18"]
+        );
+        for bad in ["67001001", "43000016", "John.3.x", "John.3.18-John.3.16"] {
+            assert!(parse_reference(&[bad.to_string()]).is_err(), "{}", bad);
+        }
+    }
+
+    fn err(input: &str) -> String {
+        match parse_reference(&[input.to_string()]) {
+            Ok(r) => panic!("{} parsed as {}", input, r),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn unknown_book_suggests_close_matches() {
+        assert_eq!(err("Jhon 3:16"), "Unknown book: Jhon. Did you mean John?");
+        assert_eq!(
+            err("Gensis 1"),
+            "Unknown book: Gensis. Did you mean Genesis?"
+        );
+        assert_eq!(
+            err("1 Corinthans 13"),
+            "Unknown book: 1 Corinthans. Did you mean 1 Corinthians?"
+        );
+        // An abbreviation prefix suggests the books it could stand for.
+        assert_eq!(
+            err("Phili 4:13"),
+            "Unknown book: Phili. Did you mean Philippians?"
+        );
+        assert_eq!(
+            err("Ju 1"),
+            "Unknown book: Ju. Did you mean Judges or Jude?"
+        );
+        assert_eq!(err("Xyzzy 1"), "Unknown book: Xyzzy");
+    }
+
+    #[test]
+    fn localized_names_parse_and_display() {
+        assert_eq!(q(&["Juan", "3:16"]).to_string(), "John 3:16");
+        assert_eq!(q(&["Génesis 1:1"]).book, "Genesis");
+        assert_eq!(q(&["Genesis 1:1"]).book, "Genesis");
+        assert_eq!(q(&["Romer 8:28"]).book, "Romans");
+        assert_eq!(q(&["1. Mose 3"]).book, "Genesis");
+        assert_eq!(q(&["Apocalipse 22:21"]).book, "Revelation");
+
+        let de = crate::locales::find_locale("de");
+        let r = q(&["Rom 8:28-9:2"]);
+        assert_eq!(r.styled_in(RefStyle::Full, de), "Römer 8:28-9:2");
+        // Abbreviated styles are standard codes, not localized.
+        assert_eq!(r.styled_in(RefStyle::Sbl, de), "Rom 8:28-9:2");
+    }
+
+    #[test]
+    fn half_verse_markers() {
+        let r = q(&["Romans 3:23a"]);
+        assert_eq!(
+            (r.verse, r.verse_part, r.verse_end),
+            (Some(23), Some('a'), None)
+        );
+        assert_eq!(r.to_string(), "Romans 3:23a");
+
+        let r = q(&["John 1:1b-3"]);
+        assert_eq!(
+            (r.verse, r.verse_part, r.verse_end),
+            (Some(1), Some('b'), Some(3))
+        );
+        assert_eq!(r.to_string(), "John 1:1b-3");
+        assert_eq!(q(&["John 1:1-3a"]).verse_end_part, Some('a'));
+        assert_eq!(q(&["John 3:16b-4:2a"]).to_string(), "John 3:16b-4:2a");
+        assert_eq!(q(&["Rom 3:23a"]).styled(RefStyle::Osis), "Rom.3.23!a");
+        assert_eq!(q(&["Rom.3.23!a"]).to_string(), "Romans 3:23a");
+        assert_eq!(q(&["Rom.3.21-Rom.3.23!a"]).to_string(), "Romans 3:21-23a");
+        // A marker belongs to a verse number, never to a book name.
+        assert_eq!(q(&["Esther 10-Job 2"]).to_string(), "Esther 10-Job 2");
+        assert!(parse_reference(&["Rom 3:23d".to_string()]).is_err());
+        assert!(parse_reference(&["Rom.3!a".to_string()]).is_err());
+    }
+
+    #[test]
+    fn following_verse_notation() {
+        let r = q(&["Psalm 119:105f"]);
+        assert_eq!((r.verse, r.verse_end), (Some(105), Some(106)));
+
+        let r = q(&["Psalm 119:105ff"]);
+        assert!(r.following);
+        assert_eq!((r.verse, r.verse_end), (Some(105), None));
+        assert_eq!(r.to_string(), "Psalms 119:105ff");
+        assert_eq!(r.styled(RefStyle::Osis), "Ps.119.105-Ps.119");
+        assert!(q(&["Ps.119.105-Ps.119"]).following);
+        assert!(parse_reference(&["Ps 119:105ff-120".to_string()]).is_err());
+        assert_eq!(qs("Rom 3:23a; 5:8ff"), vec!["Romans 3:23a", "Romans 5:8ff"]);
+    }
+
+    #[test]
+    fn styles_write_each_form() {
+        let r = q(&["1 Cor 13:4"]);
+        assert_eq!(r.styled(RefStyle::Full), "1 Corinthians 13:4");
+        assert_eq!(r.styled(RefStyle::Sbl), "1 Cor 13:4");
+        assert_eq!(r.styled(RefStyle::Short), "1Co 13:4");
+        assert_eq!(r.styled(RefStyle::Osis), "1Cor.13.4");
+
+        let r = q(&["John 3:16-4:2"]);
+        assert_eq!(r.styled(RefStyle::Short), "Jhn 3:16-4:2");
+        assert_eq!(r.styled(RefStyle::Osis), "John.3.16-John.4.2");
+        assert_eq!(q(&["Ps 23"]).styled(RefStyle::Sbl), "Ps 23");
+        assert_eq!(q(&["Gen 1-3"]).styled(RefStyle::Osis), "Gen.1-Gen.3");
+        assert_eq!(
+            q(&["1 Kings 3:5-9"]).styled(RefStyle::Osis),
+            "1Kgs.3.5-1Kgs.3.9"
+        );
+    }
+
+    #[test]
+    fn verse_lists_collapse_into_ranges() {
+        let r = q(&["John 3:16,17,18,20"]);
+        assert_eq!(r.to_string(), "John 3:16-18, 20");
+        assert_eq!(r.styled(RefStyle::Osis), "John.3.16-John.3.18 John.3.20");
+        assert_eq!(q(&["John 3:20,16-17,16"]).to_string(), "John 3:16-17, 20");
+    }
+
+    #[test]
+    fn every_style_reads_back() {
+        for book in BOOKS {
+            for style in RefStyle::value_variants() {
+                let text = style.verse(book.name, 2, 3, None);
+                let r = q(&[text.as_str()]);
+                assert_eq!(r.book, book.name, "{}", text);
+                assert_eq!((r.chapter, r.verse), (Some(2), Some(3)), "{}", text);
+            }
+        }
+    }
 }
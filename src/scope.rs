@@ -0,0 +1,193 @@
+use anyhow::{bail, Result};
+
+use crate::books::{book_position, group_names, group_range, is_old_testament};
+use crate::cli::Testament;
+use crate::reference::{lookup_book, parse_reference, ReferenceQuery};
+use crate::verses::Verse;
+
+/// One way a verse can be in scope.
+enum Part {
+    /// Books by canonical position, inclusive.
+    Books(usize, usize),
+    Testament(Testament),
+    Passage(ReferenceQuery),
+}
+
+/// Where a command may look, built from `--book`, `--testament`, and `--in`.
+/// A verse is in scope when it passes every constraint given; `--in` takes
+/// `;`-separated books, passages, and named groups (`gospels; Romans 1-8`), any
+/// of which will do.
+pub struct Scope {
+    constraints: Vec<Vec<Part>>,
+}
+
+impl Scope {
+    pub fn new(
+        book: Option<&str>,
+        testament: Option<Testament>,
+        within: Option<&str>,
+    ) -> Result<Self> {
+        let mut constraints = Vec::new();
+        if let Some(book) = book {
+            let at = book_position(lookup_book(book)?).unwrap_or(usize::MAX);
+            constraints.push(vec![Part::Books(at, at)]);
+        }
+        if let Some(testament) = testament {
+            constraints.push(vec![Part::Testament(testament)]);
+        }
+        if let Some(within) = within {
+            constraints.push(parse_parts(within)?);
+        }
+        Ok(Self { constraints })
+    }
+
+    pub fn contains(&self, verse: &Verse) -> bool {
+        self.constraints
+            .iter()
+            .all(|parts| parts.iter().any(|part| part_contains(part, verse)))
+    }
+
+    /// Whether any verse of `book` can be in scope, to load only those books.
+    pub fn touches_book(&self, book: &str) -> bool {
+        self.constraints.iter().all(|parts| {
+            parts.iter().any(|part| match part {
+                Part::Books(first, last) => {
+                    book_position(book).is_some_and(|at| *first <= at && at <= *last)
+                }
+                Part::Testament(testament) => in_testament(book, *testament),
+                Part::Passage(reference) => touches_book(reference, book),
+            })
+        })
+    }
+}
+
+fn parse_parts(input: &str) -> Result<Vec<Part>> {
+    let mut parts = Vec::new();
+    for entry in input.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        if let Some((first, last)) = group_range(entry) {
+            parts.push(Part::Books(first, last));
+            continue;
+        }
+        let reference = parse_reference(&[entry.to_string()]).map_err(|e| {
+            anyhow::anyhow!(
+                "{} (--in takes books, passages, or groups: {})",
+                e,
+                group_names().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        parts.push(match book_position(&reference.book) {
+            Some(at) if reference.is_whole_book() => Part::Books(at, at),
+            _ => Part::Passage(reference),
+        });
+    }
+    if parts.is_empty() {
+        bail!("--in needs a book, passage, or group");
+    }
+    Ok(parts)
+}
+
+fn part_contains(part: &Part, v: &Verse) -> bool {
+    match part {
+        Part::Books(first, last) => {
+            book_position(&v.book).is_some_and(|at| *first <= at && at <= *last)
+        }
+        Part::Testament(testament) => in_testament(&v.book, *testament),
+        Part::Passage(reference) => passage_contains(reference, v),
+    }
+}
+
+fn in_testament(book: &str, testament: Testament) -> bool {
+    is_old_testament(book) == Some(testament == Testament::Ot)
+}
+
+fn passage_contains(reference: &ReferenceQuery, v: &Verse) -> bool {
+    if let Some(end) = &reference.end {
+        // Same bounds as `VerseIndex::resolve` uses for a span.
+        let first = (
+            book_position(&reference.book),
+            reference.chapter.unwrap_or(0),
+            reference.verse.unwrap_or(0),
+        );
+        let last = (
+            book_position(&end.book),
+            end.chapter.unwrap_or(u16::MAX),
+            end.verse.unwrap_or(u16::MAX),
+        );
+        let key = (book_position(&v.book), v.chapter, v.verse);
+        return key.0.is_some() && first <= key && key <= last;
+    }
+    if v.book != reference.book {
+        return false;
+    }
+    let Some(chapter) = reference.chapter else {
+        return true;
+    };
+    if v.chapter != chapter {
+        return false;
+    }
+    if !reference.verse_list.is_empty() {
+        return reference.verse_list.contains(&v.verse);
+    }
+    match (reference.verse, reference.verse_end) {
+        (Some(start), Some(end)) => (start..=end).contains(&v.verse),
+        (Some(start), None) if reference.following => v.verse >= start,
+        (Some(verse), None) => v.verse == verse,
+        (None, _) => true,
+    }
+}
+
+/// Whether `book` falls within `reference`, counting every book a cross-book
+/// range passes through.
+pub fn touches_book(reference: &ReferenceQuery, book: &str) -> bool {
+    let Some(end) = &reference.end else {
+        return reference.book == book;
+    };
+    match (
+        book_position(&reference.book),
+        book_position(&end.book),
+        book_position(book),
+    ) {
+        (Some(first), Some(last), Some(at)) => first <= at && at <= last,
+        _ => reference.book == book || end.book == book,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn within(input: &str) -> Scope {
+        Scope::new(None, None, Some(input)).unwrap()
+    }
+
+    #[test]
+    fn resolves_groups_passages_and_books() {
+        let gospels = within("gospels");
+        assert!(gospels.contains(&Verse::new("Mark", 1, 1, "")));
+        assert!(!gospels.contains(&Verse::new("Acts", 1, 1, "")));
+        assert!(gospels.touches_book("John"));
+
+        let romans = within("Romans 1-8");
+        assert!(romans.contains(&Verse::new("Romans", 8, 39, "")));
+        assert!(!romans.contains(&Verse::new("Romans", 9, 1, "")));
+        assert!(!romans.touches_book("Galatians"));
+
+        let mixed = within("minor prophets; John 3:16-18; Jude");
+        assert!(mixed.contains(&Verse::new("Jonah", 2, 1, "")));
+        assert!(mixed.contains(&Verse::new("John", 3, 17, "")));
+        assert!(!mixed.contains(&Verse::new("John", 3, 19, "")));
+        assert!(mixed.contains(&Verse::new("Jude", 1, 3, "")));
+
+        assert!(within("Genesis 50-Exodus 2").contains(&Verse::new("Exodus", 1, 5, "")));
+        assert!(Scope::new(None, None, Some("epistles")).is_err());
+    }
+
+    #[test]
+    fn constraints_must_all_hold() {
+        let scope = Scope::new(Some("Romans"), Some(Testament::Nt), Some("pauline")).unwrap();
+        assert!(scope.contains(&Verse::new("Romans", 3, 23, "")));
+        assert!(!scope.contains(&Verse::new("Galatians", 1, 1, "")));
+        let scope = Scope::new(None, Some(Testament::Ot), Some("gospels")).unwrap();
+        assert!(!scope.contains(&Verse::new("John", 1, 1, "")));
+    }
+}
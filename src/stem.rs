@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Irregular and archaic forms, listed under the word they inflect. A form maps
+/// to its lemma before the suffix rules run, so `gave`, `gavest`, and `giveth`
+/// all reduce to the same stem as `give`.
+const IRREGULAR: &[(&str, &[&str])] = &[
+    (
+        "be",
+        &[
+            "am", "art", "is", "are", "was", "wast", "were", "wert", "been", "being",
+        ],
+    ),
+    ("have", &["hast", "hath", "had", "hadst", "has", "having"]),
+    (
+        "do",
+        &["dost", "doth", "doest", "doeth", "did", "didst", "done"],
+    ),
+    ("say", &["saith", "said", "saidst", "sayest"]),
+    ("give", &["gave", "gavest", "given"]),
+    ("speak", &["spake", "spakest", "spoke", "spoken"]),
+    ("go", &["goest", "goeth", "went", "wentest", "gone"]),
+    ("come", &["came", "camest"]),
+    ("take", &["took", "tookest", "taken"]),
+    ("make", &["made", "madest"]),
+    ("know", &["knew", "knewest", "known"]),
+    ("see", &["seest", "seeth", "saw", "sawest", "seen"]),
+    ("eat", &["ate", "eaten"]),
+    ("bear", &["bare", "barest", "born", "borne"]),
+    ("beget", &["begat", "begot", "begotten"]),
+    ("slay", &["slew", "slain"]),
+    ("smite", &["smote", "smitten"]),
+    ("arise", &["arose", "arisen"]),
+    ("rise", &["rose", "risen"]),
+    ("write", &["wrote", "written"]),
+    ("drink", &["drank", "drunk", "drunken"]),
+    ("fall", &["fell", "fallen"]),
+    ("flee", &["fled"]),
+    ("lead", &["led"]),
+    ("send", &["sent", "sentest"]),
+    ("bring", &["brought", "broughtest"]),
+    ("think", &["thought"]),
+    ("seek", &["sought"]),
+    ("teach", &["taught"]),
+    ("find", &["found", "foundest"]),
+    ("stand", &["stood"]),
+    ("understand", &["understood"]),
+    ("sit", &["sat"]),
+    ("forsake", &["forsook", "forsaken"]),
+    ("choose", &["chose", "chosen"]),
+    ("tell", &["told"]),
+    ("hear", &["heard"]),
+    ("keep", &["kept"]),
+    ("leave", &["left"]),
+    ("swear", &["sware", "swore", "sworn"]),
+    ("work", &["wrought"]),
+    ("abide", &["abode"]),
+    ("cleave", &["clave", "cloven"]),
+    ("get", &["gat", "got", "gotten"]),
+    ("lay", &["laid"]),
+    ("pay", &["paid"]),
+    ("sell", &["sold"]),
+    ("buy", &["bought"]),
+    ("build", &["built"]),
+    ("lose", &["lost"]),
+    ("hide", &["hid", "hidden"]),
+    ("break", &["brake", "broke", "broken"]),
+    ("begin", &["began", "begun"]),
+    ("sing", &["sang", "sung"]),
+    ("run", &["ran"]),
+    ("win", &["won"]),
+    ("meet", &["met"]),
+    ("feed", &["fed"]),
+    ("bind", &["bound"]),
+    ("hold", &["held"]),
+    ("sleep", &["slept"]),
+    ("weep", &["wept"]),
+    ("dwell", &["dwelt"]),
+    ("feel", &["felt"]),
+    ("catch", &["caught"]),
+    ("fight", &["fought"]),
+    ("throw", &["threw", "thrown"]),
+    ("grow", &["grew", "grown"]),
+    ("draw", &["drew", "drawn"]),
+    ("drive", &["drove", "driven"]),
+    ("ride", &["rode", "ridden"]),
+    ("shine", &["shone"]),
+    ("shall", &["shalt"]),
+    ("will", &["wilt"]),
+    ("can", &["canst"]),
+    ("should", &["shouldest"]),
+    ("would", &["wouldest"]),
+    ("could", &["couldest"]),
+    ("man", &["men"]),
+    ("woman", &["women"]),
+    ("child", &["children"]),
+    ("brother", &["brethren"]),
+    ("foot", &["feet"]),
+    ("tooth", &["teeth"]),
+    ("ox", &["oxen"]),
+];
+
+/// Short function words that the suffix rules would otherwise fold together
+/// (`the`/`thee`, `this`/`thy`); they only match themselves.
+const KEEP: &[&str] = &[
+    "the", "thee", "thy", "thine", "thou", "this", "these", "those", "there", "their", "them",
+    "then", "than", "that", "yes", "his", "hers", "us", "as",
+];
+
+/// Words that only look inflected (`forest` is not `for` + `-est`).
+const NOT_INFLECTED: &[&str] = &[
+    "forest", "honest", "interest", "harvest", "priest", "chest", "conquest", "request", "behest",
+    "guest", "manifest", "modest", "earnest", "tempest", "midst", "speed", "breed", "creed",
+    "hundred", "kindred",
+];
+
+fn irregular() -> &'static HashMap<&'static str, &'static str> {
+    static MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    MAP.get_or_init(|| {
+        IRREGULAR
+            .iter()
+            .flat_map(|(lemma, forms)| forms.iter().map(move |form| (*form, *lemma)))
+            .collect()
+    })
+}
+
+/// Reduce a lowercased word to a stem shared by its inflections, including the
+/// Early Modern English `-eth`/`-est`/`-edst` endings: `love`, `loved`, `loveth`,
+/// `lovest`, `lovedst`, and `loving` all become `lov`. Stems are match keys, not
+/// dictionary words; the query and the text are reduced the same way.
+pub fn stem(word: &str) -> String {
+    let word = irregular().get(word).copied().unwrap_or(word);
+    if word.chars().count() <= 2 || KEEP.contains(&word) {
+        return word.to_string();
+    }
+    let mut stem = word.to_string();
+    if !NOT_INFLECTED.contains(&word) {
+        // (suffix, shortest stem it may leave), longest suffix first.
+        for (suffix, min) in [("edst", 2), ("eth", 2), ("est", 3), ("ing", 3), ("ed", 3)] {
+            if let Some(rest) = stem.strip_suffix(suffix) {
+                if rest.chars().count() >= min {
+                    stem.truncate(rest.len());
+                    break;
+                }
+            }
+        }
+        if stem.len() == word.len()
+            && stem.ends_with('s')
+            && !(stem.ends_with("ss") || stem.ends_with("us") || stem.ends_with("is"))
+        {
+            stem.pop();
+        }
+    }
+    // `loves` -> `love` -> `lov`; `flee` and `fleeth` both -> `fl`.
+    while stem.len() > 2 && stem.ends_with('e') {
+        stem.pop();
+    }
+    // `sitteth` -> `sitt` -> `sit`, keeping the doubled l/s/z of `fall`, `bless`.
+    let chars: Vec<char> = stem.chars().collect();
+    if let [.., a, b] = chars[..] {
+        if chars.len() > 2 && a == b && !"aeiouylsz".contains(a) {
+            stem.pop();
+        }
+    }
+    // `carry`, `carried`, `carrieth` -> `carri`.
+    if stem.len() > 2 && stem.ends_with('y') {
+        stem.pop();
+        stem.push('i');
+    }
+    stem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same_stem(forms: &[&str]) {
+        let first = stem(forms[0]);
+        for form in forms {
+            assert_eq!(stem(form), first, "{} vs {}", form, forms[0]);
+        }
+    }
+
+    #[test]
+    fn folds_archaic_and_modern_inflections() {
+        same_stem(&[
+            "love", "loved", "loveth", "lovest", "lovedst", "loving", "loves",
+        ]);
+        same_stem(&[
+            "give", "gave", "gavest", "given", "giveth", "givest", "giving",
+        ]);
+        same_stem(&["bless", "blessed", "blesseth", "blessing"]);
+        same_stem(&["sit", "sat", "sitteth", "sitting"]);
+        same_stem(&["see", "seeth", "seest", "seeing", "saw", "seen"]);
+        same_stem(&["flee", "fleeth", "fled"]);
+        same_stem(&["carry", "carried", "carrieth"]);
+        same_stem(&["city", "cities"]);
+        same_stem(&["say", "saith", "said", "saying"]);
+        same_stem(&["king", "kings"]);
+    }
+
+    #[test]
+    fn keeps_words_that_only_look_inflected() {
+        assert_ne!(stem("the"), stem("thee"));
+        assert_ne!(stem("this"), stem("thy"));
+        assert_ne!(stem("forest"), stem("for"));
+        assert_ne!(stem("seed"), stem("see"));
+        assert_eq!(stem("jesus"), "jesus");
+        assert_eq!(stem("thing"), "thing");
+        assert_eq!(stem("rest"), "rest");
+    }
+}
@@ -0,0 +1,138 @@
+/// Edit distance between two strings, counting insertions, deletions,
+/// substitutions, and swaps of adjacent characters as one edit each (optimal
+/// string alignment), so the common typo `Jhon` is one edit from `John`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return n.max(m);
+    }
+
+    let idx = |i: usize, j: usize| i * (m + 1) + j;
+    let mut dp = vec![0usize; (n + 1) * (m + 1)];
+    for i in 0..=n {
+        dp[idx(i, 0)] = i;
+    }
+    for j in 0..=m {
+        dp[idx(0, j)] = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (dp[idx(i - 1, j)] + 1)
+                .min(dp[idx(i, j - 1)] + 1)
+                .min(dp[idx(i - 1, j - 1)] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(dp[idx(i - 2, j - 2)] + 1);
+            }
+            dp[idx(i, j)] = best;
+        }
+    }
+    dp[idx(n, m)]
+}
+
+/// Join alternatives for a message: `A`, `A or B`, `A, B or C`.
+pub fn join_or(items: &[&str]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [init @ .., last] => format!("{} or {}", init.join(", "), last),
+    }
+}
+
+/// Fold accented Latin letters to their ASCII base, keeping case (`Génesis` ->
+/// `Genesis`, `Römer` -> `Romer`, `ß` -> `ss`). Other characters pass through.
+pub fn fold_diacritics(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        let lower = ch.to_lowercase().next().unwrap_or(ch);
+        let base = match lower {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+            'æ' => "ae",
+            'ç' => "c",
+            'è' | 'é' | 'ê' | 'ë' => "e",
+            'ì' | 'í' | 'î' | 'ï' => "i",
+            'ñ' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+            'œ' => "oe",
+            'ù' | 'ú' | 'û' | 'ü' => "u",
+            'ý' | 'ÿ' => "y",
+            'ß' => "ss",
+            _ => {
+                out.push(ch);
+                continue;
+            }
+        };
+        if ch.is_uppercase() {
+            out.push_str(&base.to_ascii_uppercase());
+        } else {
+            out.push_str(base);
+        }
+    }
+    out
+}
+
+/// At most the last `width` characters of `text`, starting with `…` when it
+/// was cut: the left column of a keyword-in-context line.
+pub fn clip_left(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let tail: String = text.chars().skip(count - width + 1).collect();
+    format!("…{}", tail)
+}
+
+/// At most the first `width` characters of `text`, ending with `…` when it was
+/// cut: the right column of a keyword-in-context line.
+pub fn clip_right(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let head: String = text.chars().take(width - 1).collect();
+    format!("{}…", head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_basic_edits() {
+        assert_eq!(edit_distance("john", "john"), 0);
+        assert_eq!(edit_distance("jhon", "john"), 1);
+        assert_eq!(edit_distance("gensis", "genesis"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn join_or_reads_naturally() {
+        assert_eq!(join_or(&["John"]), "John");
+        assert_eq!(join_or(&["Joel", "John"]), "Joel or John");
+        assert_eq!(join_or(&["Joel", "John", "Jonah"]), "Joel, John or Jonah");
+    }
+
+    #[test]
+    fn fold_diacritics_keeps_case() {
+        assert_eq!(fold_diacritics("Génesis"), "Genesis");
+        assert_eq!(fold_diacritics("Éxodo"), "Exodo");
+        assert_eq!(fold_diacritics("Sprüche"), "Spruche");
+        assert_eq!(fold_diacritics("João 3:16"), "Joao 3:16");
+    }
+
+    #[test]
+    fn clips_context_columns() {
+        assert_eq!(clip_left("In the beginning God ", 8), "…ng God ");
+        assert_eq!(clip_right(" created the heaven", 9), " created…");
+        assert_eq!(clip_left("short", 8), "short");
+        assert_eq!(clip_right("Ἀμήν, amen", 4), "Ἀμή…");
+    }
+}
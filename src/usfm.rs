@@ -0,0 +1,309 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::books::{book_from_usfm, book_position};
+use crate::cache::Imported;
+use crate::verses::Verse;
+
+/// Markers whose content runs to the end of the line and is not verse text:
+/// identification, titles, headings, and introductions.
+const LINE_MARKERS: &[&str] = &[
+    "ide", "usfm", "h", "h1", "h2", "h3", "toc1", "toc2", "toc3", "toca1", "toca2", "toca3", "mt",
+    "mt1", "mt2", "mt3", "mt4", "mte", "mte1", "mte2", "ms", "ms1", "ms2", "ms3", "mr", "s", "s1",
+    "s2", "s3", "s4", "sr", "r", "d", "sp", "rem", "sts", "cl", "cd", "restore",
+];
+
+/// Notes and other spans dropped along with their content, up to the closing
+/// `\marker*`: footnotes, cross references, figures, and alternate numbering.
+const NOTE_MARKERS: &[&str] = &[
+    "f", "fe", "ef", "x", "ex", "fig", "va", "vp", "ca", "cat", "rq",
+];
+
+/// Whether a source looks like USFM: it opens with an `\id` marker.
+pub fn is_usfm(raw: &str) -> bool {
+    raw.starts_with("\\id ")
+}
+
+/// Concatenate the `.usfm`/`.sfm` books in a directory, in file-name order
+/// (which for eBible.org downloads is Bible order).
+pub fn read_dir(dir: &Path) -> Result<String> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed reading {}", dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("usfm") || ext.eq_ignore_ascii_case("sfm")
+                })
+        })
+        .collect();
+    if files.is_empty() {
+        bail!("No .usfm or .sfm files in {}", dir.display());
+    }
+    files.sort();
+    let mut out = String::new();
+    for path in files {
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Failed reading {}", path.display()))?;
+        out.push_str(raw.strip_prefix('\u{feff}').unwrap_or(&raw).trim_start());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+enum Token<'a> {
+    Marker(&'a str),
+    Text(&'a str),
+}
+
+/// What the next run of text starts with.
+#[derive(Clone, Copy, PartialEq)]
+enum Expect {
+    Text,
+    BookCode,
+    Chapter,
+    Verse,
+}
+
+/// Parse one or more USFM books into verses. `\id`, `\c`, and `\v` give the
+/// book, chapter, and verse; headings, introductions, footnotes (`\f`), and
+/// cross references (`\x`) are dropped, and character markup (`\wj`, `\nd`,
+/// `\w word|lemma`) keeps its text without the markers or attributes. Verses
+/// come out in Bible order. A verse bridge (`\v 17-18`) is stored under its
+/// first number only, so the others read as gaps to `translation verify`.
+pub fn parse(raw: &str) -> Result<Imported> {
+    let mut verses = Vec::new();
+    let mut book: Option<String> = None;
+    let mut chapter = 0u16;
+    let mut current: Option<u16> = None;
+    let mut text = String::new();
+    let mut expect = Expect::Text;
+    let mut skip_line = false;
+    let mut note: Option<&str> = None;
+    // Inside a milestone (`\zaln-s |x-strong="G1722"\*`), up to its `\*`.
+    let mut milestone = false;
+
+    for token in tokenize(raw) {
+        match token {
+            Token::Marker(marker) => {
+                let marker = marker.trim_start_matches('+');
+                if std::mem::take(&mut milestone) && marker == "*" {
+                    continue;
+                }
+                if marker.ends_with("-s") || marker.ends_with("-e") {
+                    milestone = true;
+                    continue;
+                }
+                if let Some(open) = note {
+                    if marker.strip_suffix('*') == Some(open) {
+                        note = None;
+                    }
+                    continue;
+                }
+                if NOTE_MARKERS.contains(&marker) {
+                    note = Some(marker);
+                    continue;
+                }
+                match marker {
+                    "id" => {
+                        flush(&mut verses, &book, chapter, current.take(), &mut text);
+                        expect = Expect::BookCode;
+                    }
+                    "c" => {
+                        flush(&mut verses, &book, chapter, current.take(), &mut text);
+                        expect = Expect::Chapter;
+                    }
+                    "v" => {
+                        flush(&mut verses, &book, chapter, current.take(), &mut text);
+                        expect = Expect::Verse;
+                    }
+                    // Character markup closes without a break.
+                    _ if marker.ends_with('*') => {}
+                    _ if LINE_MARKERS.contains(&marker) || is_intro(marker) => skip_line = true,
+                    // Paragraph and poetry markers separate words.
+                    _ => text.push(' '),
+                }
+            }
+            Token::Text(mut run) => {
+                if note.is_some() || milestone {
+                    continue;
+                }
+                if expect != Expect::Text {
+                    let trimmed = run.trim_start();
+                    let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+                    let (word, rest) = trimmed.split_at(end);
+                    match expect {
+                        Expect::BookCode => {
+                            book = Some(book_from_usfm(word).unwrap_or(word).to_string());
+                            chapter = 0;
+                            skip_line = true;
+                        }
+                        Expect::Chapter => chapter = leading_number(word).unwrap_or(0),
+                        Expect::Verse => current = leading_number(word),
+                        Expect::Text => {}
+                    }
+                    expect = Expect::Text;
+                    run = rest;
+                }
+                if skip_line {
+                    match run.find('\n') {
+                        Some(at) => {
+                            skip_line = false;
+                            run = &run[at..];
+                        }
+                        None => continue,
+                    }
+                }
+                if current.is_some() {
+                    // Attributes (`\w grace|strong="G5485"\w*`) follow a `|`.
+                    text.push_str(run.split('|').next().unwrap_or(""));
+                }
+            }
+        }
+    }
+    flush(&mut verses, &book, chapter, current.take(), &mut text);
+
+    if verses.is_empty() {
+        bail!("No verses found in USFM source");
+    }
+    verses.sort_by_key(|v| book_position(&v.book).unwrap_or(usize::MAX));
+    Ok(Imported {
+        verses,
+        ..Imported::default()
+    })
+}
+
+/// Introduction markers (`\ip`, `\is1`, `\imt`, ...), but not `\it` (italics).
+fn is_intro(marker: &str) -> bool {
+    marker.starts_with('i') && marker != "it"
+}
+
+/// Split USFM into markers (`\v`, `\wj*`, milestones like `\qt-s` and their
+/// closing `\*`) and the text between them. A single space after an opening
+/// marker belongs to the marker.
+fn tokenize(raw: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let bytes = raw.as_bytes();
+    let mut at = 0;
+    while at < bytes.len() {
+        if bytes[at] != b'\\' {
+            at += 1;
+            continue;
+        }
+        if start < at {
+            tokens.push(Token::Text(&raw[start..at]));
+        }
+        let name_start = at + 1;
+        let mut end = name_start;
+        while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'+') {
+            end += 1;
+        }
+        // A milestone's start or end: `\zaln-s`, `\qt-e`.
+        if end > name_start
+            && bytes.get(end) == Some(&b'-')
+            && matches!(bytes.get(end + 1), Some(b's' | b'e'))
+            && !bytes.get(end + 2).is_some_and(u8::is_ascii_alphanumeric)
+        {
+            end += 2;
+        }
+        if end < bytes.len() && bytes[end] == b'*' {
+            end += 1;
+            tokens.push(Token::Marker(&raw[name_start..end]));
+            at = end;
+        } else {
+            tokens.push(Token::Marker(&raw[name_start..end]));
+            at = if end < bytes.len() && bytes[end] == b' ' {
+                end + 1
+            } else {
+                end
+            };
+        }
+        start = at;
+    }
+    if start < bytes.len() {
+        tokens.push(Token::Text(&raw[start..]));
+    }
+    tokens
+}
+
+/// The number at the start of a chapter or verse number (`16a`, `1-2` -> 16, 1).
+fn leading_number(word: &str) -> Option<u16> {
+    let end = word
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(word.len());
+    word[..end].parse().ok()
+}
+
+fn flush(
+    verses: &mut Vec<Verse>,
+    book: &Option<String>,
+    chapter: u16,
+    current: Option<u16>,
+    text: &mut String,
+) {
+    let body = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    text.clear();
+    let (Some(book), Some(verse)) = (book, current) else {
+        return;
+    };
+    if chapter == 0 || body.is_empty() {
+        return;
+    }
+    verses.push(Verse {
+        book: book.clone(),
+        chapter,
+        verse,
+        text: body,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_verses_and_strips_notes_and_markup() {
+        let raw = r#"\id JHN - Test Bible
+\h John
+\mt1 The Gospel According to John
+\c 3
+\s1 God's Love for the World
+\p
+\v 16 \wj For God so loved the world,\wj*\f + \fr 3:16 \ft Or, \fq only\f* that he gave his \add only\add* Son,
+\q1 \w whosoever|strong="G3956"\w* believeth\x - \xo 3:16 \xt Rom 5:8\x* shall not perish.
+\v 17-18 For God sent not his Son.
+\id 1JN
+\c 4
+\v 8 He that loveth not knoweth not \nd God\nd*; for God is love.
+\v 9 \zaln-s |x-strong="G1722"\*\w In|x-occurrence="1"\w*\zaln-e\* the beginning \qt-s |who="x"\*was\qt-e\*.
+"#;
+        assert!(is_usfm(raw));
+        let verses = parse(raw).unwrap().verses;
+        assert_eq!(verses.len(), 4);
+        assert_eq!(
+            (verses[0].book.as_str(), verses[0].chapter, verses[0].verse),
+            ("John", 3, 16)
+        );
+        assert_eq!(
+            verses[0].text,
+            "For God so loved the world, that he gave his only Son, whosoever believeth shall not perish."
+        );
+        // The bridge `17-18` keeps its text under verse 17; there is no verse 18.
+        assert_eq!(
+            (verses[1].verse, verses[1].text.as_str()),
+            (17, "For God sent not his Son.")
+        );
+        assert!(!verses.iter().any(|v| v.chapter == 3 && v.verse == 18));
+        assert_eq!(verses[2].book, "1 John");
+        assert_eq!(
+            verses[2].text,
+            "He that loveth not knoweth not God; for God is love."
+        );
+        // USFM 3 milestones leave no trace, attributes included.
+        assert_eq!(verses[3].text, "In the beginning was.");
+    }
+}
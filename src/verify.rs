@@ -0,0 +1,262 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::books::{book_position, expected_chapters, BOOKS};
+use crate::verses::Verse;
+
+/// A problem with an installed translation's text.
+#[derive(Debug, PartialEq)]
+pub enum Issue {
+    /// A book name outside the canon (e.g. the `Unknown` a source without book
+    /// names leaves behind), with its verse count.
+    UnknownBook(String, usize),
+    /// Canonical books with no verses at all.
+    MissingBooks(Vec<&'static str>),
+    /// Chapters absent from a book that has others.
+    MissingChapters(String, Vec<u16>),
+    /// A book that ends one chapter early, with the chapter it ends at (often
+    /// a versification difference: Malachi 4 as Malachi 3:19-24).
+    FewerChapters(String, u16),
+    /// Chapters past the book's expected count (often a versification difference).
+    ExtraChapters(String, Vec<u16>),
+    /// Verse numbers skipped within a chapter.
+    VerseGaps(String, u16, Vec<u16>),
+    /// A (book, chapter, verse) key that occurs more than once, and how often.
+    Duplicate(String, u16, u16, usize),
+    /// A verse whose text is empty or only whitespace.
+    EmptyText(String, u16, u16),
+}
+
+impl Issue {
+    /// Whether the issue may just be the translation's own versification
+    /// rather than damaged text; such issues are noted, not counted as problems.
+    pub fn is_versification(&self) -> bool {
+        matches!(self, Issue::FewerChapters(..) | Issue::ExtraChapters(..))
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::UnknownBook(name, count) => {
+                write!(
+                    f,
+                    "unknown book '{}' ({} verse{})",
+                    name,
+                    count,
+                    plural(*count)
+                )
+            }
+            Issue::MissingBooks(books) => write!(
+                f,
+                "missing {} book{}: {}",
+                books.len(),
+                plural(books.len()),
+                book_runs(books)
+            ),
+            Issue::MissingChapters(book, chapters) => write!(
+                f,
+                "{} is missing chapter{} {}",
+                book,
+                plural(chapters.len()),
+                runs(chapters)
+            ),
+            Issue::FewerChapters(book, last) => write!(
+                f,
+                "{} ends at chapter {} rather than the expected {}",
+                book,
+                last,
+                expected_chapters(book).unwrap_or(0)
+            ),
+            Issue::ExtraChapters(book, chapters) => write!(
+                f,
+                "{} has chapter{} {} beyond the expected {}",
+                book,
+                plural(chapters.len()),
+                runs(chapters),
+                expected_chapters(book).unwrap_or(0)
+            ),
+            Issue::VerseGaps(book, chapter, verses) => write!(
+                f,
+                "{} {} is missing verse{} {}",
+                book,
+                chapter,
+                plural(verses.len()),
+                runs(verses)
+            ),
+            Issue::Duplicate(book, chapter, verse, count) => {
+                write!(f, "{} {}:{} appears {} times", book, chapter, verse, count)
+            }
+            Issue::EmptyText(book, chapter, verse) => {
+                write!(f, "{} {}:{} has no text", book, chapter, verse)
+            }
+        }
+    }
+}
+
+/// Check a translation against the canonical book list and the expected
+/// chapters per book: missing books and chapters, gaps in verse numbering,
+/// duplicate references, unknown book names, and empty verses. A chapter's
+/// last verses can't be checked, as verse counts vary between versifications;
+/// for the same reason a book one chapter short or with extra chapters is only
+/// a versification issue (see `Issue::is_versification`).
+pub fn verify(verses: &[Verse]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut unknown: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut seen: HashMap<(&str, u16, u16), usize> = HashMap::new();
+    // Verse numbers by book position and chapter.
+    let mut chapters: BTreeMap<(usize, u16), Vec<u16>> = BTreeMap::new();
+    let mut empty = Vec::new();
+    for v in verses {
+        *seen.entry((&v.book, v.chapter, v.verse)).or_insert(0) += 1;
+        if v.text.trim().is_empty() {
+            empty.push(Issue::EmptyText(v.book.clone(), v.chapter, v.verse));
+        }
+        match book_position(&v.book) {
+            Some(at) => chapters.entry((at, v.chapter)).or_default().push(v.verse),
+            None => *unknown.entry(&v.book).or_insert(0) += 1,
+        }
+    }
+
+    for (name, count) in unknown {
+        issues.push(Issue::UnknownBook(name.to_string(), count));
+    }
+    let missing: Vec<&'static str> = BOOKS
+        .iter()
+        .enumerate()
+        .filter(|(at, _)| !chapters.keys().any(|(book, _)| book == at))
+        .map(|(_, b)| b.name)
+        .collect();
+    if !missing.is_empty() {
+        issues.push(Issue::MissingBooks(missing));
+    }
+    for (at, book) in BOOKS.iter().enumerate() {
+        let present: Vec<u16> = chapters
+            .range((at, 0)..=(at, u16::MAX))
+            .map(|((_, chapter), _)| *chapter)
+            .collect();
+        if present.is_empty() {
+            continue;
+        }
+        let expected = expected_chapters(book.name).unwrap_or(0);
+        let last = present.iter().copied().max().unwrap_or(0);
+        // One chapter short is how other versifications number some books;
+        // more than that is missing text.
+        let short_by_one = last + 1 == expected;
+        let upto = if short_by_one { last } else { expected };
+        let absent: Vec<u16> = (1..=upto).filter(|c| !present.contains(c)).collect();
+        if !absent.is_empty() {
+            issues.push(Issue::MissingChapters(book.name.to_string(), absent));
+        }
+        if short_by_one {
+            issues.push(Issue::FewerChapters(book.name.to_string(), last));
+        }
+        let extra: Vec<u16> = present.into_iter().filter(|c| *c > expected).collect();
+        if !extra.is_empty() {
+            issues.push(Issue::ExtraChapters(book.name.to_string(), extra));
+        }
+    }
+    for ((at, chapter), numbers) in &chapters {
+        let last = numbers.iter().copied().max().unwrap_or(0);
+        let gaps: Vec<u16> = (1..=last).filter(|n| !numbers.contains(n)).collect();
+        if !gaps.is_empty() {
+            issues.push(Issue::VerseGaps(
+                BOOKS[*at].name.to_string(),
+                *chapter,
+                gaps,
+            ));
+        }
+    }
+    let mut duplicates: Vec<_> = seen.into_iter().filter(|(_, count)| *count > 1).collect();
+    duplicates.sort_by_key(|((book, chapter, verse), _)| {
+        (book_position(book).unwrap_or(usize::MAX), *chapter, *verse)
+    });
+    for ((book, chapter, verse), count) in duplicates {
+        issues.push(Issue::Duplicate(book.to_string(), chapter, verse, count));
+    }
+    issues.extend(empty);
+    issues
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// Ascending numbers as runs: `[3, 4, 5, 9]` -> `3-5, 9`.
+fn runs(numbers: &[u16]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for group in numbers.chunk_by(|a, b| a + 1 == *b) {
+        match group {
+            [only] => out.push(only.to_string()),
+            [first, .., last] => out.push(format!("{}-{}", first, last)),
+            [] => {}
+        }
+    }
+    out.join(", ")
+}
+
+/// Canonical books as runs: `Genesis–Malachi, Jude`.
+fn book_runs(books: &[&str]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let positions: Vec<usize> = books.iter().filter_map(|b| book_position(b)).collect();
+    for group in positions.chunk_by(|a, b| a + 1 == *b) {
+        match group {
+            [only] => out.push(BOOKS[*only].name.to_string()),
+            [first, .., last] => out.push(format!("{}–{}", BOOKS[*first].name, BOOKS[*last].name)),
+            [] => {}
+        }
+    }
+    out.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_gaps_duplicates_and_unknown_books() {
+        let mut verses = Vec::new();
+        for book in BOOKS {
+            for chapter in 1..=expected_chapters(book.name).unwrap() {
+                verses.push(Verse::new(book.name, chapter, 1, "text"));
+            }
+        }
+        assert!(verify(&verses).is_empty());
+
+        // Matthew 2:16 dropped, the way a past source did.
+        verses.retain(|v| {
+            v.book != "Jude"
+                && !(v.book == "Psalms" && v.chapter == 23)
+                && !(v.book == "Malachi" && v.chapter == 4)
+        });
+        for n in (2..=23).filter(|n| *n != 16) {
+            verses.push(Verse::new("Matthew", 2, n, "text"));
+        }
+        verses.push(Verse::new("John", 3, 1, "again"));
+        verses.push(Verse::new("John", 3, 2, " "));
+        verses.push(Verse::new("Unknown", 1, 1, "text"));
+        let issues = verify(&verses);
+        let messages: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "unknown book 'Unknown' (1 verse)",
+                "missing 1 book: Jude",
+                "Psalms is missing chapter 23",
+                "Malachi ends at chapter 3 rather than the expected 4",
+                "Matthew 2 is missing verse 16",
+                "John 3:1 appears 2 times",
+                "John 3:2 has no text",
+            ]
+        );
+        let versification: Vec<bool> = issues.iter().map(Issue::is_versification).collect();
+        assert_eq!(
+            versification,
+            vec![false, false, false, true, false, false, false]
+        );
+    }
+}
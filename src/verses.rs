@@ -5,7 +5,9 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
-use crate::reference::ReferenceQuery;
+use crate::books::book_position;
+use crate::corpus;
+use crate::reference::{RangeEnd, ReferenceQuery};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Verse {
@@ -15,6 +17,18 @@ pub struct Verse {
     pub text: String,
 }
 
+impl Verse {
+    #[cfg(test)]
+    pub fn new(book: &str, chapter: u16, verse: u16, text: &str) -> Self {
+        Self {
+            book: book.to_string(),
+            chapter,
+            verse,
+            text: text.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct VerseRef {
     pub book: &'static str,
@@ -22,10 +36,25 @@ pub struct VerseRef {
     pub verse: u16,
 }
 
+/// Load a translation's verses. When preload has written a current binary
+/// corpus beside the JSONL it is memory-mapped instead; otherwise every JSONL
+/// line is parsed.
 pub fn load_verses(path: &Path) -> Result<Vec<Verse>> {
+    load_books(path, |_| true)
+}
+
+/// Like `load_verses`, but keeps only the books `keep` accepts. A binary corpus
+/// never decodes the others, so reading one passage stays fast.
+pub fn load_books(path: &Path, keep: impl Fn(&str) -> bool) -> Result<Vec<Verse>> {
+    if let Some(corpus) = corpus::open_current(path) {
+        if let Ok(verses) = corpus.load(&keep) {
+            return Ok(verses);
+        }
+    }
     let file = File::open(path).with_context(|| format!("KJV not found at {}", path.display()))?;
     let reader = BufReader::new(file);
     let mut verses = Vec::new();
+    let mut total = 0;
     for (idx, line) in reader.lines().enumerate() {
         let line = line.with_context(|| format!("Failed reading line {}", idx + 1))?;
         if line.trim().is_empty() {
@@ -33,14 +62,43 @@ pub fn load_verses(path: &Path) -> Result<Vec<Verse>> {
         }
         let verse: Verse = serde_json::from_str(&line)
             .with_context(|| format!("Invalid JSON on line {}", idx + 1))?;
-        verses.push(verse);
+        total += 1;
+        if keep(&verse.book) {
+            verses.push(verse);
+        }
     }
-    if verses.is_empty() {
+    if total == 0 {
         bail!("KJV cache is empty at {}", path.display());
     }
     Ok(verses)
 }
 
+/// The verses at `positions` in stored order (the search index's candidates),
+/// ascending. A current binary corpus decodes just those; otherwise the whole
+/// JSONL is read and the rest dropped.
+pub fn load_positions(path: &Path, positions: &[usize]) -> Result<Vec<Verse>> {
+    if let Some(corpus) = corpus::open_current(path) {
+        if let Ok(verses) = corpus.load_at(positions) {
+            return Ok(verses);
+        }
+    }
+    let verses = load_verses(path)?;
+    Ok(positions
+        .iter()
+        .filter_map(|&p| verses.get(p).cloned())
+        .collect())
+}
+
+/// Sort key for Bible order: book position, chapter, verse. Books outside the
+/// canon sort last.
+pub fn canonical_order(v: &Verse) -> (usize, u16, u16) {
+    (
+        book_position(&v.book).unwrap_or(usize::MAX),
+        v.chapter,
+        v.verse,
+    )
+}
+
 pub fn max_chapter(verses: &[Verse], book: &str) -> Option<u16> {
     verses
         .iter()
@@ -96,6 +154,10 @@ impl<'a> VerseIndex<'a> {
     /// Resolve a parsed reference into the matching verses. Returns an error for
     /// a whole-book reference (which has no concrete verse set to render).
     pub fn resolve(&self, query: &ReferenceQuery) -> Result<Vec<&'a Verse>> {
+        if let Some(end) = &query.end {
+            return self.resolve_span(query, end);
+        }
+
         let Some(chapter) = query.chapter else {
             bail!("Chapter is required to resolve verses");
         };
@@ -108,7 +170,7 @@ impl<'a> VerseIndex<'a> {
                 }
             }
             if out.is_empty() {
-                bail!("No verses found for {} {}", query.book, chapter);
+                return Err(self.not_found(query, &query.book, Some(chapter)));
             }
             return Ok(out);
         }
@@ -119,29 +181,212 @@ impl<'a> VerseIndex<'a> {
                     .filter_map(|v| self.get(&query.book, chapter, v))
                     .collect();
                 if out.is_empty() {
-                    bail!(
-                        "No verses found for {} {}:{}-{}",
-                        query.book,
-                        chapter,
-                        start,
-                        end
-                    );
+                    return Err(self.not_found(query, &query.book, Some(chapter)));
+                }
+                Ok(out)
+            }
+            // `ff`: the rest of the chapter from `start`.
+            (Some(start), None) if query.following => {
+                let out: Vec<&Verse> = self
+                    .chapter(&query.book, chapter)
+                    .into_iter()
+                    .filter(|v| v.verse >= start)
+                    .collect();
+                if out.is_empty() {
+                    return Err(self.not_found(query, &query.book, Some(chapter)));
                 }
                 Ok(out)
             }
             (Some(verse), None) => {
                 let v = self
                     .get(&query.book, chapter, verse)
-                    .ok_or_else(|| anyhow::anyhow!("Verse not found"))?;
+                    .ok_or_else(|| self.not_found(query, &query.book, Some(chapter)))?;
                 Ok(vec![v])
             }
             (None, _) => {
                 let out = self.chapter(&query.book, chapter);
                 if out.is_empty() {
-                    bail!("No verses found for {} {}", query.book, chapter);
+                    return Err(self.not_found(query, &query.book, Some(chapter)));
                 }
                 Ok(out)
             }
         }
     }
+
+    /// Highest chapter number of a book in the loaded corpus (0 if absent).
+    pub fn chapter_count(&self, book: &str) -> u16 {
+        self.by_chapter
+            .keys()
+            .filter(|(b, _)| *b == book)
+            .map(|(_, c)| *c)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Highest verse number of a chapter in the loaded corpus (0 if absent).
+    pub fn verse_count(&self, book: &str, chapter: u16) -> u16 {
+        self.by_chapter
+            .get(&(book, chapter))
+            .and_then(|indices| indices.iter().map(|&i| self.verses[i].verse).max())
+            .unwrap_or(0)
+    }
+
+    /// A "not found" error for `what` that states the real bounds of the loaded
+    /// corpus at the point it fell outside them, e.g.
+    /// `John 3:99 not found: John 3 has 36 verses`.
+    pub fn not_found(
+        &self,
+        what: impl std::fmt::Display,
+        book: &str,
+        chapter: Option<u16>,
+    ) -> anyhow::Error {
+        let chapters = self.chapter_count(book);
+        if chapters == 0 {
+            return anyhow::anyhow!("{} not found: {} is not in this translation", what, book);
+        }
+        let verses = chapter.map_or(0, |c| self.verse_count(book, c));
+        if verses == 0 {
+            return anyhow::anyhow!("{} not found: {} has {} chapters", what, book, chapters);
+        }
+        anyhow::anyhow!(
+            "{} not found: {} {} has {} verses",
+            what,
+            book,
+            chapter.unwrap_or(0),
+            verses
+        )
+    }
+
+    /// Every verse from the start of `query` through `end`, in canonical order,
+    /// crossing chapter and book boundaries as needed.
+    fn resolve_span(&self, query: &ReferenceQuery, end: &RangeEnd) -> Result<Vec<&'a Verse>> {
+        let first = (
+            book_position(&query.book),
+            query.chapter.unwrap_or(0),
+            query.verse.unwrap_or(0),
+        );
+        let last = (
+            book_position(&end.book),
+            end.chapter.unwrap_or(u16::MAX),
+            end.verse.unwrap_or(u16::MAX),
+        );
+        let key = |v: &Verse| (book_position(&v.book), v.chapter, v.verse);
+        let mut out: Vec<&'a Verse> = self
+            .verses
+            .iter()
+            .filter(|v| {
+                let k = key(v);
+                k.0.is_some() && first <= k && k <= last
+            })
+            .collect();
+        if out.is_empty() {
+            return Err(self.not_found(query, &query.book, query.chapter));
+        }
+        out.sort_by_key(|v| key(v));
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reference::parse_reference;
+
+    fn corpus() -> Vec<Verse> {
+        let mut verses = Vec::new();
+        for (book, chapters) in [("Genesis", 50u16), ("Exodus", 3), ("John", 4)] {
+            for chapter in 1..=chapters {
+                for verse in 1..=3 {
+                    let text = format!("{} {}:{}", book, chapter, verse);
+                    verses.push(Verse::new(book, chapter, verse, &text));
+                }
+            }
+        }
+        verses
+    }
+
+    fn resolve(verses: &[Verse], reference: &str) -> Vec<String> {
+        let index = VerseIndex::build(verses);
+        let query = parse_reference(&[reference.to_string()]).unwrap();
+        index
+            .resolve(&query)
+            .unwrap()
+            .iter()
+            .map(|v| v.text.clone())
+            .collect()
+    }
+
+    #[test]
+    fn resolves_cross_chapter_range() {
+        let verses = corpus();
+        assert_eq!(
+            resolve(&verses, "John 3:2-4:1"),
+            vec!["John 3:2", "John 3:3", "John 4:1"]
+        );
+    }
+
+    #[test]
+    fn resolves_chapter_range() {
+        let verses = corpus();
+        let out = resolve(&verses, "Genesis 1-2");
+        assert_eq!(out.len(), 6);
+        assert_eq!(out.first().unwrap(), "Genesis 1:1");
+        assert_eq!(out.last().unwrap(), "Genesis 2:3");
+    }
+
+    #[test]
+    fn resolves_cross_book_range_in_canonical_order() {
+        // Source order must not matter: shuffle books before indexing.
+        let mut verses = corpus();
+        verses.reverse();
+        assert_eq!(
+            resolve(&verses, "Genesis 50:3-Exodus 1:2"),
+            vec!["Genesis 50:3", "Exodus 1:1", "Exodus 1:2"]
+        );
+    }
+
+    fn resolve_err(verses: &[Verse], reference: &str) -> String {
+        let index = VerseIndex::build(verses);
+        let query = parse_reference(&[reference.to_string()]).unwrap();
+        match index.resolve(&query) {
+            Ok(found) => panic!("{} resolved to {} verses", reference, found.len()),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn errors_state_real_bounds() {
+        let verses = corpus();
+        assert_eq!(
+            resolve_err(&verses, "John 3:99"),
+            "John 3:99 not found: John 3 has 3 verses"
+        );
+        assert_eq!(
+            resolve_err(&verses, "John 3:7-9"),
+            "John 3:7-9 not found: John 3 has 3 verses"
+        );
+        assert_eq!(
+            resolve_err(&verses, "John 9"),
+            "John 9 not found: John has 4 chapters"
+        );
+        assert_eq!(
+            resolve_err(&verses, "John 9:1-10:2"),
+            "John 9:1-10:2 not found: John has 4 chapters"
+        );
+        assert_eq!(
+            resolve_err(&verses, "Jude 1:1"),
+            "Jude 1:1 not found: Jude is not in this translation"
+        );
+    }
+
+    #[test]
+    fn following_runs_to_the_end_of_the_chapter() {
+        let verses = corpus();
+        assert_eq!(resolve(&verses, "John 2:2ff"), vec!["John 2:2", "John 2:3"]);
+        assert_eq!(resolve(&verses, "John 2:3f"), vec!["John 2:3"]);
+        assert_eq!(
+            resolve(&verses, "John 2:1b-2"),
+            vec!["John 2:1", "John 2:2"]
+        );
+    }
 }
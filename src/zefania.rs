@@ -0,0 +1,135 @@
+use anyhow::{bail, Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::books::{book_by_number, normalize_book};
+use crate::cache::Imported;
+use crate::osis::{attribute, collapse, local_name};
+use crate::verses::Verse;
+
+/// Whether a source looks like a Zefania XML bible.
+pub fn is_zefania(raw: &str) -> bool {
+    raw.starts_with('<') && (raw.contains("<XMLBIBLE") || raw.contains("<BIBLEBOOK"))
+}
+
+/// Parse a Zefania bible: `<BIBLEBOOK bnumber>` / `<CHAPTER cnumber>` /
+/// `<VERS vnumber>`. Books are found by number (1-66), else by `bname`; notes
+/// inside a verse are dropped. The title and language come from
+/// `<INFORMATION>`, or the `biblename` attribute for the title.
+pub fn parse(raw: &str) -> Result<Imported> {
+    let mut reader = Reader::from_str(raw);
+    let mut imported = Imported::default();
+    let mut book: Option<String> = None;
+    let mut chapter: Option<u16> = None;
+    let mut current: Option<u16> = None;
+    let mut text = String::new();
+    let mut in_note = 0usize;
+    // The `<INFORMATION>` field being read (`title`, `language`).
+    let mut field: Option<String> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .with_context(|| format!("Invalid Zefania XML at byte {}", reader.buffer_position()))?;
+        match event {
+            Event::Start(e) => match local_name(&e).as_str() {
+                "XMLBIBLE" => imported.title = attribute(&e, "biblename"),
+                "title" | "language" => field = Some(local_name(&e)),
+                "BIBLEBOOK" => {
+                    let by_number = attribute(&e, "bnumber")
+                        .and_then(|n| n.parse().ok())
+                        .and_then(book_by_number);
+                    book = by_number.map(str::to_string).or_else(|| {
+                        attribute(&e, "bname")
+                            .map(|name| normalize_book(&name).map(str::to_string).unwrap_or(name))
+                    });
+                }
+                "CHAPTER" => chapter = attribute(&e, "cnumber").and_then(|n| n.parse().ok()),
+                "VERS" => {
+                    text.clear();
+                    current = attribute(&e, "vnumber").and_then(|n| n.parse().ok());
+                }
+                "NOTE" | "REMARK" | "XREF" => in_note += 1,
+                _ => {}
+            },
+            Event::Empty(e) if local_name(&e) == "BR" => text.push(' '),
+            Event::End(e) => match std::str::from_utf8(e.local_name().as_ref()).unwrap_or("") {
+                "title" | "language" => field = None,
+                "VERS" => {
+                    let body = collapse(&text);
+                    text.clear();
+                    if let (Some(book), Some(chapter), Some(verse)) =
+                        (&book, chapter, current.take())
+                    {
+                        if !body.is_empty() {
+                            imported.verses.push(Verse {
+                                book: book.clone(),
+                                chapter,
+                                verse,
+                                text: body,
+                            });
+                        }
+                    }
+                }
+                "NOTE" | "REMARK" | "XREF" => in_note = in_note.saturating_sub(1),
+                _ => {}
+            },
+            Event::Text(e) => {
+                let value = e.unescape().context("Invalid Zefania text")?;
+                if current.is_some() {
+                    if in_note == 0 {
+                        text.push_str(&value);
+                    }
+                } else {
+                    match field.as_deref() {
+                        Some("title") => imported.title = Some(collapse(&value)),
+                        Some("language") => imported.language = Some(collapse(&value)),
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if imported.verses.is_empty() {
+        bail!("No verses found in Zefania source");
+    }
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_books_by_number_and_name() {
+        let raw = r#"<?xml version="1.0" encoding="utf-8"?>
+<XMLBIBLE biblename="KJV">
+  <INFORMATION><title>King James Version</title><language>ENG</language></INFORMATION>
+  <BIBLEBOOK bnumber="1" bname="Genesis">
+    <CHAPTER cnumber="1">
+      <VERS vnumber="1">In the beginning God created the heaven and the earth.</VERS>
+    </CHAPTER>
+  </BIBLEBOOK>
+  <BIBLEBOOK bname="Juan">
+    <CHAPTER cnumber="3">
+      <VERS vnumber="16">For God so loved<NOTE>Or, so much.</NOTE> the <STYLE fs="italic">world</STYLE>.</VERS>
+    </CHAPTER>
+  </BIBLEBOOK>
+</XMLBIBLE>"#;
+        assert!(is_zefania(raw));
+        let imported = parse(raw).unwrap();
+        assert_eq!(imported.title.as_deref(), Some("King James Version"));
+        assert_eq!(imported.language.as_deref(), Some("ENG"));
+        let verses = &imported.verses;
+        assert_eq!(verses.len(), 2);
+        assert_eq!((verses[0].book.as_str(), verses[0].verse), ("Genesis", 1));
+        assert_eq!(
+            (verses[1].book.as_str(), verses[1].chapter, verses[1].verse),
+            ("John", 3, 16)
+        );
+        assert_eq!(verses[1].text, "For God so loved the world.");
+    }
+}